@@ -0,0 +1,266 @@
+//! Relay server for Typetris versus matches. Clients join a room and get paired with the next
+//! client to join the same room. From then on, everything one of them sends is forwarded to the
+//! other; the server never looks at the games themselves.
+//!
+//! Usage: `typetris-relay [port]`
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    io::ErrorKind,
+    net::{TcpListener, TcpStream},
+    sync::{
+        Arc, Mutex,
+        mpsc::{self, Receiver, Sender},
+    },
+    thread,
+    time::Duration,
+};
+
+use tungstenite::{Message as Frame, WebSocket};
+use typetris::{
+    game::versus::Player,
+    protocol::{DEFAULT_PORT, Message},
+};
+
+/// How long a connection waits for its client before checking for messages from the opponent.
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+enum Envelope {
+    /// An opponent has joined, and messages for them go to `opponent`.
+    Paired {
+        opponent: Sender<Envelope>,
+        start: Message,
+    },
+    Relay(Message),
+}
+
+/// Clients waiting for an opponent, by room.
+type Lobby = Arc<Mutex<HashMap<String, Sender<Envelope>>>>;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let port = match std::env::args().nth(1) {
+        Some(arg) => arg.parse()?,
+        None => DEFAULT_PORT,
+    };
+    let listener = TcpListener::bind(("0.0.0.0", port))?;
+    println!("listening on ws://{}", listener.local_addr()?);
+    serve(listener);
+    Ok(())
+}
+
+fn serve(listener: TcpListener) {
+    let lobby = Lobby::default();
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        let lobby = lobby.clone();
+        thread::spawn(move || {
+            let peer = stream.peer_addr();
+            if let Err(e) = handle(stream, &lobby) {
+                eprintln!("{peer:?}: {e}");
+            }
+        });
+    }
+}
+
+fn handle(stream: TcpStream, lobby: &Lobby) -> Result<(), Box<dyn Error>> {
+    let mut socket = tungstenite::accept(stream)?;
+    socket.get_ref().set_read_timeout(Some(POLL_INTERVAL))?;
+    let (sender, inbox) = mpsc::channel();
+    let mut opponent = None;
+    let ret = relay(&mut socket, lobby, &sender, &inbox, &mut opponent);
+    if let Some(opponent) = opponent {
+        let _ = opponent.send(Envelope::Relay(Message::OpponentLeft));
+    }
+    ret
+}
+
+fn relay(
+    socket: &mut WebSocket<TcpStream>,
+    lobby: &Lobby,
+    sender: &Sender<Envelope>,
+    inbox: &Receiver<Envelope>,
+    opponent: &mut Option<Sender<Envelope>>,
+) -> Result<(), Box<dyn Error>> {
+    // Joining again while waiting would pair the client with itself.
+    let mut waiting = false;
+    loop {
+        match socket.read() {
+            Ok(Frame::Text(text)) => match Message::from_json(text.as_str()) {
+                Ok(Message::Join { room }) if opponent.is_none() && !waiting => {
+                    if let Some((found, start)) = join(lobby, room, sender) {
+                        *opponent = Some(found);
+                        socket.send(Frame::text(start.to_json()))?;
+                    } else {
+                        waiting = true;
+                    }
+                }
+                Ok(message) => {
+                    if let Some(opponent) = opponent {
+                        let _ = opponent.send(Envelope::Relay(message));
+                    }
+                }
+                Err(e) => eprintln!("ignoring malformed message: {e}"),
+            },
+            Ok(Frame::Close(_)) | Err(tungstenite::Error::ConnectionClosed) => return Ok(()),
+            Ok(_) => (),
+            Err(tungstenite::Error::Io(e))
+                if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        while let Ok(envelope) = inbox.try_recv() {
+            let message = match envelope {
+                Envelope::Paired {
+                    opponent: found,
+                    start,
+                } => {
+                    *opponent = Some(found);
+                    start
+                }
+                Envelope::Relay(message) => message,
+            };
+            socket.send(Frame::text(message.to_json()))?;
+        }
+    }
+}
+
+/// Pair the client with whoever is waiting in `room`, or wait there if nobody is. On success,
+/// return the opponent and the [`Message::Start`] for the client that just joined.
+fn join(
+    lobby: &Lobby,
+    room: String,
+    sender: &Sender<Envelope>,
+) -> Option<(Sender<Envelope>, Message)> {
+    let mut lobby = lobby.lock().unwrap();
+    if let Some(waiting) = lobby.remove(&room) {
+        let seed = rand::random();
+        let paired = Envelope::Paired {
+            opponent: sender.clone(),
+            start: Message::Start {
+                seed,
+                player: Player::One,
+            },
+        };
+        // The waiting client might have left in the meantime.
+        if waiting.send(paired).is_ok() {
+            let start = Message::Start {
+                seed,
+                player: Player::Two,
+            };
+            return Some((waiting, start));
+        }
+    }
+    lobby.insert(room, sender.clone());
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use typetris::game::Event;
+
+    fn client(address: &str) -> WebSocket<tungstenite::stream::MaybeTlsStream<TcpStream>> {
+        tungstenite::connect(format!("ws://{address}")).unwrap().0
+    }
+
+    fn send(socket: &mut WebSocket<impl std::io::Read + std::io::Write>, message: Message) {
+        socket.send(Frame::text(message.to_json())).unwrap();
+    }
+
+    fn receive(socket: &mut WebSocket<impl std::io::Read + std::io::Write>) -> Message {
+        loop {
+            if let Frame::Text(text) = socket.read().unwrap() {
+                return Message::from_json(text.as_str()).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn pairs_and_relays() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        thread::spawn(move || serve(listener));
+
+        let mut a = client(&address);
+        let mut b = client(&address);
+        let mut c = client(&address);
+        send(
+            &mut a,
+            Message::Join {
+                room: "lunch".into(),
+            },
+        );
+        send(
+            &mut c,
+            Message::Join {
+                room: "other".into(),
+            },
+        );
+        // Make sure `a` is waiting before `b` joins.
+        thread::sleep(Duration::from_millis(50));
+        send(
+            &mut b,
+            Message::Join {
+                room: "lunch".into(),
+            },
+        );
+
+        let Message::Start { seed, player } = receive(&mut a) else {
+            panic!("expected a start message");
+        };
+        assert_eq!(player, Player::One);
+        assert_eq!(
+            receive(&mut b),
+            Message::Start {
+                seed,
+                player: Player::Two
+            }
+        );
+
+        let input = Message::Input {
+            event: Event::Type('x'),
+        };
+        send(&mut a, input.clone());
+        assert_eq!(receive(&mut b), input);
+        send(&mut b, Message::GameOver);
+        assert_eq!(receive(&mut a), Message::GameOver);
+
+        b.close(None).unwrap();
+        assert_eq!(receive(&mut a), Message::OpponentLeft);
+    }
+
+    #[test]
+    fn joining_twice() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        thread::spawn(move || serve(listener));
+
+        let mut a = client(&address);
+        let mut b = client(&address);
+        let join = || Message::Join {
+            room: "lunch".into(),
+        };
+        send(&mut a, join());
+        send(&mut a, join());
+        thread::sleep(Duration::from_millis(50));
+        send(&mut b, join());
+
+        // `a` isn't paired with itself, and still gets paired with `b`.
+        let Message::Start { seed, player } = receive(&mut a) else {
+            panic!("expected a start message");
+        };
+        assert_eq!(player, Player::One);
+        assert_eq!(
+            receive(&mut b),
+            Message::Start {
+                seed,
+                player: Player::Two
+            }
+        );
+        send(&mut b, Message::GameOver);
+        assert_eq!(receive(&mut a), Message::GameOver);
+    }
+}
@@ -0,0 +1,194 @@
+//! Short effects drawn over the board in response to the notices a game queues, so players can
+//! see what just happened.
+
+use typetris::game::block::{Orientation, Segment};
+use typetris::game::{Game, Notice};
+use web_sys::CanvasRenderingContext2d;
+
+use super::swatch::Swatch;
+
+const SETTLE_DURATION: f64 = 150.0;
+const CLEAR_DURATION: f64 = 400.0;
+const LEVEL_UP_DURATION: f64 = 800.0;
+const GAME_OVER_DURATION: f64 = 600.0;
+/// Time it takes a block that moved sideways to cover half the distance left to its new cell on
+/// screen.
+const SLIDE_HALF_LIFE: f64 = 25.0;
+
+#[derive(Debug, Clone)]
+enum Effect {
+    /// Flash the cells of a block that landed.
+    Flash(Vec<Segment>),
+    /// Light up cleared rows, and shrink away the cells that were in them.
+    Dissolve { rows: Vec<u8>, cells: Vec<Segment> },
+    /// Light up the edge of the board.
+    LevelUp,
+    /// Tint the whole board.
+    GameOver,
+}
+
+#[derive(Debug, Clone)]
+struct Animation {
+    effect: Effect,
+    elapsed: f64,
+    duration: f64,
+}
+
+impl Animation {
+    /// How far along the animation is, from 0 to 1.
+    #[inline]
+    fn progress(&self) -> f64 {
+        (self.elapsed / self.duration).min(1.0)
+    }
+}
+
+/// The focused block easing into the cell it moved to sideways.
+#[derive(Debug, Clone, Default)]
+struct Slide {
+    /// The focused block's word, orientation and column when it was last followed.
+    focus: Option<(String, Orientation, u8)>,
+    /// How far right of its cell the focused block is drawn. Negative is left.
+    offset: f64,
+}
+
+/// The animations playing on one board.
+#[derive(Debug, Clone, Default)]
+pub(super) struct Animations {
+    playing: Vec<Animation>,
+    slide: Slide,
+}
+
+impl Animations {
+    /// Start the animations for `notices`.
+    pub(super) fn extend(&mut self, notices: impl IntoIterator<Item = Notice>) {
+        for notice in notices {
+            let (effect, duration) = match notice {
+                Notice::BlockSettled(block) => {
+                    (Effect::Flash(block.segments().collect()), SETTLE_DURATION)
+                }
+                Notice::RowsCleared { rows, blocks } => (
+                    Effect::Dissolve {
+                        rows,
+                        cells: blocks.iter().flat_map(|b| b.segments()).collect(),
+                    },
+                    CLEAR_DURATION,
+                ),
+                Notice::LevelUp(_) | Notice::Finished => (Effect::LevelUp, LEVEL_UP_DURATION),
+                Notice::GameOver => (Effect::GameOver, GAME_OVER_DURATION),
+                Notice::Typed { .. } | Notice::Dropped | Notice::BlockSpawned => continue,
+            };
+            self.playing.push(Animation {
+                effect,
+                elapsed: 0.0,
+                duration,
+            });
+        }
+    }
+
+    /// Advance every animation by `delta_time`, and drop the ones that have finished.
+    pub(super) fn tick(&mut self, delta_time: f64) {
+        for animation in &mut self.playing {
+            animation.elapsed += delta_time;
+        }
+        self.playing.retain(|a| a.elapsed < a.duration);
+        self.slide.offset *= 0.5f64.powf(delta_time / SLIDE_HALF_LIFE);
+        if self.slide.offset.abs() < 0.01 {
+            self.slide.offset = 0.0;
+        }
+    }
+
+    /// Catch up with where `game`'s focused block is. If it's the same block as last time and
+    /// it moved sideways, it slides over from where it was drawn.
+    pub(super) fn follow(&mut self, game: &Game) {
+        let focus = game
+            .board()
+            .get_focused()
+            .map(|b| (b.assigned_text(), b.orientation(), b.position().x));
+        match (&self.slide.focus, focus) {
+            (Some((text, orientation, x)), Some((new_text, new_orientation, new_x)))
+                if text == new_text && *orientation == new_orientation =>
+            {
+                self.slide.offset += *x as f64 - new_x as f64;
+                self.slide.focus = Some((text.clone(), *orientation, new_x));
+            }
+            _ => {
+                self.slide.offset = 0.0;
+                self.slide.focus =
+                    focus.map(|(text, orientation, x)| (text.into(), orientation, x));
+            }
+        }
+    }
+
+    /// How far right of its cell the focused block should be drawn. Negative is left.
+    #[inline]
+    pub(super) fn slide(&self) -> f64 {
+        self.slide.offset
+    }
+
+    #[inline]
+    pub(super) fn is_playing(&self) -> bool {
+        !self.playing.is_empty() || self.slide.offset != 0.0
+    }
+
+    /// Draw every animation over a board of `width` by `height` cells, each `cell_width` by
+    /// `cell_height` pixels.
+    pub(super) fn draw(
+        &self,
+        context: &CanvasRenderingContext2d,
+        swatch: &Swatch,
+        (width, height): (u8, u8),
+        (cell_width, cell_height): (f64, f64),
+    ) {
+        let board_width = width as f64 * cell_width;
+        let board_height = height as f64 * cell_height;
+        for animation in &self.playing {
+            let t = animation.progress();
+            match &animation.effect {
+                Effect::Flash(segments) => {
+                    context.set_global_alpha(0.6 * (1.0 - t));
+                    context.set_fill_style_str(&swatch.text_color);
+                    for segment in segments {
+                        context.fill_rect(
+                            segment.position.x as f64 * cell_width,
+                            segment.position.y as f64 * cell_height,
+                            segment.len as f64 * cell_width,
+                            cell_height,
+                        );
+                    }
+                }
+                Effect::Dissolve { rows, cells } => {
+                    context.set_global_alpha(0.5 * (1.0 - t));
+                    context.set_fill_style_str(&swatch.text_color);
+                    for &y in rows {
+                        context.fill_rect(0.0, y as f64 * cell_height, board_width, cell_height);
+                    }
+                    context.set_global_alpha(1.0 - t);
+                    context.set_fill_style_str(&swatch.success_color);
+                    let (inset_x, inset_y) = (t * cell_width / 2.0, t * cell_height / 2.0);
+                    for segment in cells {
+                        for i in 0..segment.len {
+                            context.fill_rect(
+                                (segment.position.x + i) as f64 * cell_width + inset_x,
+                                segment.position.y as f64 * cell_height + inset_y,
+                                cell_width - inset_x * 2.0,
+                                cell_height - inset_y * 2.0,
+                            );
+                        }
+                    }
+                }
+                Effect::LevelUp => {
+                    context.set_global_alpha(1.0 - t);
+                    context.set_stroke_style_str(&swatch.reticle_color);
+                    context.set_line_width(cell_width.min(cell_height) * 0.3);
+                    context.stroke_rect(0.0, 0.0, board_width, board_height);
+                }
+                Effect::GameOver => {
+                    context.set_global_alpha(0.5 * (1.0 - t));
+                    context.set_fill_style_str(&swatch.error_color);
+                    context.fill_rect(0.0, 0.0, board_width, board_height);
+                }
+            }
+        }
+        context.set_global_alpha(1.0);
+    }
+}
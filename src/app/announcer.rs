@@ -0,0 +1,77 @@
+//! What to read out to screen reader users, who can't see the canvas.
+
+use typetris::game::{Game, Notice};
+
+/// Keeps the text of an ARIA live region up to date with what's happening in a game.
+#[derive(Debug, Clone, Default)]
+pub(super) struct Announcer {
+    /// Word of the focused block when it was last announced.
+    focused: Option<String>,
+    score: usize,
+    text: String,
+}
+
+impl Announcer {
+    #[inline]
+    pub(super) fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Work out what to announce after `notices` happened in `game`, and return whether it
+    /// changed.
+    pub(super) fn update(&mut self, game: &Game, notices: &[Notice]) -> bool {
+        if game.is_splash() {
+            return false;
+        }
+        let mut parts = vec![];
+        for notice in notices {
+            match notice {
+                Notice::Dropped => parts.push("Dropped.".to_string()),
+                Notice::RowsCleared { rows, .. } if rows.len() == 1 => {
+                    parts.push("Cleared a row.".to_string())
+                }
+                Notice::RowsCleared { rows, .. } => {
+                    parts.push(format!("Cleared {} rows.", rows.len()))
+                }
+                Notice::LevelUp(level) => parts.push(format!("Level {level}.")),
+                Notice::GameOver => parts.push("Game over.".to_string()),
+                Notice::Finished => parts.push("Finished.".to_string()),
+                _ => (),
+            }
+        }
+
+        let focus = game.board().get_focused();
+        let typed = notices.iter().rev().find_map(|notice| match notice {
+            Notice::Typed { correct } => Some(*correct),
+            _ => None,
+        });
+        if let (Some(correct), Some(focus)) = (typed, focus) {
+            parts.push(format!(
+                "{}{}, {} of {}.",
+                if correct { "" } else { "Wrong. " },
+                focus.input_text(),
+                focus.input_text().len(),
+                focus.assigned_text().len(),
+            ));
+        }
+
+        let focused = focus.map(|b| b.assigned_text().to_string());
+        if focused != self.focused {
+            if let Some(word) = &focused {
+                parts.push(format!("Next word: {word}."));
+            }
+            self.focused = focused;
+        }
+
+        if game.score() != self.score {
+            self.score = game.score();
+            parts.push(format!("Score {}.", self.score));
+        }
+
+        if parts.is_empty() {
+            return false;
+        }
+        self.text = parts.join(" ");
+        true
+    }
+}
@@ -0,0 +1,186 @@
+//! Sound effects, synthesized with the Web Audio API from the notices a game queues.
+//!
+//! There's one audio context for the whole page, created the first time a sound plays, since
+//! browsers only allow sound after the player has interacted with the page. Volume and mute are
+//! remembered between visits.
+
+use std::cell::RefCell;
+
+use gloo::storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+use typetris::game::Notice;
+use web_sys::{AudioContext, AudioContextState, OscillatorType, wasm_bindgen::JsValue};
+
+const STORAGE_KEY: &str = "typetris.audio";
+
+/// Volume and mute, as saved in local storage.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub(super) struct AudioSettings {
+    /// From 0 to 1.
+    pub(super) volume: f64,
+    pub(super) muted: bool,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            volume: 0.5,
+            muted: false,
+        }
+    }
+}
+
+impl AudioSettings {
+    #[inline]
+    pub(super) fn load() -> Self {
+        LocalStorage::get(STORAGE_KEY).unwrap_or_default()
+    }
+}
+
+#[derive(Default)]
+struct Audio {
+    context: Option<AudioContext>,
+    settings: Option<AudioSettings>,
+}
+
+thread_local! {
+    static AUDIO: RefCell<Audio> = RefCell::default();
+}
+
+/// Change and save the volume and mute settings.
+pub(super) fn set_settings(settings: AudioSettings) {
+    // Not being able to save isn't worth bothering the player about.
+    let _ = LocalStorage::set(STORAGE_KEY, settings);
+    AUDIO.with_borrow_mut(|audio| audio.settings = Some(settings));
+}
+
+/// Play the sounds for `notices`.
+pub(super) fn play(notices: &[Notice]) {
+    if notices.is_empty() {
+        return;
+    }
+    AUDIO.with_borrow_mut(|audio| {
+        let settings = *audio.settings.get_or_insert_with(AudioSettings::load);
+        if settings.muted || settings.volume <= 0.0 {
+            return;
+        }
+        // Nothing useful to do if the browser refuses.
+        let _ = audio.play(notices, settings.volume as f32);
+    });
+}
+
+/// A note sliding from one pitch to another and fading out.
+#[derive(Debug, Clone, Copy)]
+struct Tone {
+    wave: OscillatorType,
+    /// Pitch at the start, in Hz.
+    from: f32,
+    /// Pitch at the end, in Hz.
+    to: f32,
+    /// Length, in seconds.
+    duration: f64,
+    /// Loudness at full volume.
+    gain: f32,
+}
+
+impl Tone {
+    const fn new(wave: OscillatorType, from: f32, to: f32, duration: f64, gain: f32) -> Self {
+        Self {
+            wave,
+            from,
+            to,
+            duration,
+            gain,
+        }
+    }
+
+    /// A note that holds its pitch.
+    const fn note(wave: OscillatorType, pitch: f32, duration: f64, gain: f32) -> Self {
+        Self::new(wave, pitch, pitch, duration, gain)
+    }
+}
+
+const CLICK: Tone = Tone::new(OscillatorType::Square, 1_200.0, 1_000.0, 0.03, 0.1);
+const BUZZ: Tone = Tone::new(OscillatorType::Sawtooth, 180.0, 140.0, 0.1, 0.2);
+const DROP: Tone = Tone::new(OscillatorType::Sine, 600.0, 250.0, 0.12, 0.3);
+const THUD: Tone = Tone::new(OscillatorType::Triangle, 160.0, 90.0, 0.1, 0.4);
+const CLEAR_PITCHES: [f32; 4] = [523.3, 659.3, 784.0, 1_046.5];
+const LEVEL_UP_PITCHES: [f32; 5] = [392.0, 523.3, 659.3, 784.0, 1_046.5];
+const GAME_OVER_PITCHES: [f32; 4] = [392.0, 329.6, 261.6, 196.0];
+
+/// The tones to play for `notice`, each with how long after the notice it starts, in seconds.
+fn tones(notice: &Notice) -> Vec<(f64, Tone)> {
+    let arpeggio = |pitches: &[f32], gap: f64, tone: fn(f32) -> Tone| {
+        pitches
+            .iter()
+            .enumerate()
+            .map(|(i, &pitch)| (i as f64 * gap, tone(pitch)))
+            .collect()
+    };
+    match notice {
+        Notice::Typed { correct: true } => vec![(0.0, CLICK)],
+        Notice::Typed { correct: false } => vec![(0.0, BUZZ)],
+        Notice::Dropped => vec![(0.0, DROP)],
+        Notice::BlockSettled(_) => vec![(0.0, THUD)],
+        // One more note for every row cleared at once.
+        Notice::RowsCleared { rows, .. } => {
+            let len = (rows.len() + 1).min(CLEAR_PITCHES.len());
+            arpeggio(&CLEAR_PITCHES[..len], 0.07, |pitch| {
+                Tone::note(OscillatorType::Square, pitch, 0.12, 0.15)
+            })
+        }
+        Notice::LevelUp(_) | Notice::Finished => arpeggio(&LEVEL_UP_PITCHES, 0.09, |pitch| {
+            Tone::note(OscillatorType::Triangle, pitch, 0.2, 0.3)
+        }),
+        Notice::GameOver => arpeggio(&GAME_OVER_PITCHES, 0.18, |pitch| {
+            Tone::new(OscillatorType::Triangle, pitch, pitch * 0.97, 0.3, 0.35)
+        }),
+        Notice::BlockSpawned => vec![],
+    }
+}
+
+impl Audio {
+    fn context(&mut self) -> Result<&AudioContext, JsValue> {
+        if self.context.is_none() {
+            self.context = Some(AudioContext::new()?);
+        }
+        let context = self.context.as_ref().unwrap();
+        if context.state() == AudioContextState::Suspended {
+            let _ = context.resume()?;
+        }
+        Ok(context)
+    }
+
+    fn play(&mut self, notices: &[Notice], volume: f32) -> Result<(), JsValue> {
+        let context = self.context()?;
+        let now = context.current_time();
+        for (delay, tone) in notices.iter().flat_map(tones) {
+            play_tone(context, tone, now + delay, volume)?;
+        }
+        Ok(())
+    }
+}
+
+/// Play `tone` at `start` on the clock of `context`.
+fn play_tone(context: &AudioContext, tone: Tone, start: f64, volume: f32) -> Result<(), JsValue> {
+    let end = start + tone.duration;
+    let oscillator = context.create_oscillator()?;
+    oscillator.set_type(tone.wave);
+    oscillator.frequency().set_value_at_time(tone.from, start)?;
+    oscillator
+        .frequency()
+        .exponential_ramp_to_value_at_time(tone.to, end)?;
+    let envelope = context.create_gain()?;
+    envelope
+        .gain()
+        .set_value_at_time(tone.gain * volume, start)?;
+    // Exponential ramps can't reach zero.
+    envelope
+        .gain()
+        .exponential_ramp_to_value_at_time(0.001, end)?;
+    oscillator.connect_with_audio_node(&envelope)?;
+    envelope.connect_with_audio_node(&context.destination())?;
+    oscillator.start_with_when(start)?;
+    oscillator.stop_with_when(end)?;
+    Ok(())
+}
@@ -0,0 +1,253 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use typetris::game::{
+    Game,
+    block::{Block, Kind},
+    board::BoardPosition,
+};
+use web_sys::{
+    CanvasRenderingContext2d, HtmlCanvasElement, ResizeObserver,
+    wasm_bindgen::{JsCast, closure::Closure},
+};
+use yew::{Callback, NodeRef};
+
+use super::animation::Animations;
+use super::swatch::Swatch;
+
+#[inline]
+pub(super) fn context_2d(canvas: &HtmlCanvasElement) -> CanvasRenderingContext2d {
+    canvas
+        .get_context("2d")
+        .unwrap()
+        .unwrap()
+        .dyn_into()
+        .unwrap()
+}
+
+/// Size the backing store of `canvas` to its on-screen size in device pixels, and return the new
+/// width and height.
+pub(super) fn fit_to_screen(canvas: &HtmlCanvasElement) -> (f64, f64) {
+    let rect = canvas.get_bounding_client_rect();
+    let dpr = web_sys::window().unwrap().device_pixel_ratio();
+    let canvas_width = rect.width() * dpr;
+    let canvas_height = rect.height() * dpr;
+    canvas.set_width(canvas_width as _);
+    canvas.set_height(canvas_height as _);
+    (canvas_width, canvas_height)
+}
+
+/// Watches canvases for changes to their size on screen, and the window for changes to its device
+/// pixel ratio, either of which leaves their backing stores the wrong size and the board blurry or
+/// stretched.
+pub(super) struct ResizeWatcher {
+    observer: ResizeObserver,
+    _on_resize: Closure<dyn FnMut()>,
+    /// Set by the observer, and cleared once the backing stores have been resized.
+    resized: Rc<Cell<bool>>,
+    device_pixel_ratio: f64,
+}
+
+impl ResizeWatcher {
+    /// Make a watcher that calls `callback` when an observed canvas is resized, so that it can be
+    /// redrawn even if nothing else changed.
+    pub(super) fn new(callback: Callback<()>) -> Self {
+        let resized = Rc::new(Cell::new(false));
+        let on_resize = {
+            let resized = resized.clone();
+            Closure::<dyn FnMut()>::new(move || {
+                resized.set(true);
+                callback.emit(());
+            })
+        };
+        Self {
+            observer: ResizeObserver::new(on_resize.as_ref().unchecked_ref()).unwrap(),
+            _on_resize: on_resize,
+            resized,
+            device_pixel_ratio: web_sys::window().unwrap().device_pixel_ratio(),
+        }
+    }
+
+    /// Start watching the canvas behind `node`. Watching one twice does nothing.
+    pub(super) fn observe(&self, node: &NodeRef) {
+        if let Some(canvas) = node.cast::<HtmlCanvasElement>() {
+            self.observer.observe(&canvas);
+        }
+    }
+
+    /// Whether the backing stores need resizing since this was last called.
+    pub(super) fn take_stale(&mut self) -> bool {
+        let device_pixel_ratio = web_sys::window().unwrap().device_pixel_ratio();
+        let dpr_changed = device_pixel_ratio != self.device_pixel_ratio;
+        self.device_pixel_ratio = device_pixel_ratio;
+        self.resized.take() || dpr_changed
+    }
+}
+
+impl Drop for ResizeWatcher {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+/// Draw the board of `game` and its animations onto the canvas behind `node`, sizing its backing
+/// store to the screen first if `fit` is set.
+pub(super) fn render_board(
+    node: &NodeRef,
+    game: &Game,
+    animations: &Animations,
+    swatch: &Swatch,
+    fit: bool,
+) {
+    let canvas: HtmlCanvasElement = node.cast().unwrap();
+    let context = context_2d(&canvas);
+    let (canvas_width, canvas_height) = if fit {
+        fit_to_screen(&canvas)
+    } else {
+        (canvas.width() as f64, canvas.height() as f64)
+    };
+    draw_board(
+        &context,
+        game,
+        animations,
+        swatch,
+        canvas_width,
+        canvas_height,
+    );
+}
+
+/// Draw the board of `game` and its animations onto a canvas whose backing store is
+/// `canvas_width` by `canvas_height` pixels. Falling blocks are drawn part of the way to their
+/// next cell, and the focused block slides into place after moving sideways.
+pub(super) fn draw_board(
+    context: &CanvasRenderingContext2d,
+    game: &Game,
+    animations: &Animations,
+    swatch: &Swatch,
+    canvas_width: f64,
+    canvas_height: f64,
+) {
+    let board = game.board();
+    let cell_width = canvas_width / board.width() as f64;
+    let cell_height = canvas_height / board.height() as f64;
+
+    context.set_fill_style_str(&swatch.bg_color);
+    context.fill_rect(0.0, 0.0, canvas_width, canvas_height);
+    context.set_font(format!("normal {:.0}px system-ui", cell_width * 0.7).as_str());
+    context.set_text_align("center");
+    context.set_text_baseline("middle");
+    context.set_line_width(5.0);
+
+    for (index, block) in board.blocks().iter().enumerate() {
+        let focused = board.get_focused_index() == Some(index);
+        let dx = if focused { animations.slide() } else { 0.0 };
+        let dy = game.fall_offset(index);
+        let to_canvas = |position: BoardPosition| {
+            (
+                (position.x as f64 + dx) * cell_width,
+                (position.y as f64 + dy) * cell_height,
+            )
+        };
+        let cell_of = |block: &Block, i: usize| to_canvas(block.cell_position(i).unwrap());
+
+        context.begin_path();
+        for segment in block.segments() {
+            let (x, y) = to_canvas(segment.position);
+            context.rect(x, y, segment.len as f64 * cell_width, cell_height);
+        }
+        context.set_fill_style_str(match block.kind() {
+            Kind::Bomb => &swatch.bomb_color,
+            Kind::Ice => &swatch.ice_color,
+            Kind::Locking | Kind::Locked => &swatch.locked_color,
+            Kind::Normal | Kind::Wildcard if block.is_interactable() => &swatch.regular_block_color,
+            Kind::Normal | Kind::Wildcard => &swatch.disabled_block_color,
+        });
+        context.fill();
+
+        if focused {
+            for (i, ch) in block
+                .input_text()
+                .chars()
+                .take(block.assigned_text().len())
+                .enumerate()
+            {
+                let (x, y) = cell_of(block, i);
+                let correct = block.matches(i, ch);
+                let color = if correct {
+                    &swatch.success_color
+                } else {
+                    &swatch.error_color
+                };
+                context.set_fill_style_str(color);
+                context.fill_rect(x, y, cell_width, cell_height);
+                if !correct {
+                    // Underline mistakes so they don't rely on colour alone.
+                    context.set_fill_style_str(&swatch.text_color);
+                    context.fill_rect(
+                        x + cell_width * 0.15,
+                        y + cell_height * 0.82,
+                        cell_width * 0.7,
+                        cell_height * 0.08,
+                    );
+                }
+            }
+        }
+
+        // Garbage is filler, so it has nothing to read.
+        if !block.is_garbage() {
+            context.set_fill_style_str(&swatch.text_color);
+            for i in 0..block.assigned_text().len() {
+                let (x, y) = cell_of(block, i);
+                let (x, y) = (x + cell_width / 2.0, y + cell_height / 2.0);
+                context
+                    .fill_text(&block.assigned_text()[i..i + 1], x, y)
+                    .unwrap();
+            }
+        }
+
+        for segment in block.segments() {
+            let (x, y) = to_canvas(segment.position);
+            for i in 1..segment.len {
+                let x = i as f64 * cell_width + x;
+                context.move_to(x, y);
+                context.line_to(x, y + cell_height);
+            }
+        }
+        context.set_stroke_style_str(&swatch.separator_color);
+        context.stroke();
+
+        if focused {
+            let n = block.input_text().len();
+            if n < block.assigned_text().len() {
+                let (x, y) = cell_of(block, n);
+                context.begin_path();
+                context
+                    .arc(
+                        x + cell_width * 0.5,
+                        y + cell_height * 0.5,
+                        cell_width.min(cell_height) * 0.4,
+                        0.0,
+                        std::f64::consts::PI * 2.0,
+                    )
+                    .unwrap();
+                context.move_to(x + cell_width * 0.1, y + cell_height * 0.1);
+                context.line_to(x + cell_width * 0.2, y + cell_height * 0.2);
+                context.move_to(x + cell_width * 0.1, y + cell_height * 0.9);
+                context.line_to(x + cell_width * 0.2, y + cell_height * 0.8);
+                context.move_to(x + cell_width * 0.9, y + cell_height * 0.1);
+                context.line_to(x + cell_width * 0.8, y + cell_height * 0.2);
+                context.move_to(x + cell_width * 0.9, y + cell_height * 0.9);
+                context.line_to(x + cell_width * 0.8, y + cell_height * 0.8);
+                context.set_stroke_style_str(&swatch.reticle_color);
+                context.stroke();
+            }
+        }
+    }
+
+    animations.draw(
+        context,
+        swatch,
+        (board.width(), board.height()),
+        (cell_width, cell_height),
+    );
+}
@@ -0,0 +1,130 @@
+//! Typing a text of the player's own: a field to paste it into, and a game of its words.
+
+use gloo::storage::{LocalStorage, Storage};
+use typetris::game::layout::Layout;
+use typetris::game::passage::{LongWords, Punctuation, TextOptions};
+use web_sys::{HtmlSelectElement, HtmlTextAreaElement};
+use yew::prelude::*;
+
+use super::game::Game;
+use super::rules::Rules;
+use super::theme::Theme;
+
+const STORAGE_KEY: &str = "typetris.text";
+
+#[derive(Debug, Clone, Properties, PartialEq, Eq)]
+pub(crate) struct Props {
+    pub(crate) theme: &'static Theme,
+    pub(crate) layout: Layout,
+    pub(crate) rules: Rules,
+}
+
+/// A `<select>` of `options`, calling `onchange` with the one picked.
+fn view_select<T: Copy + PartialEq + 'static>(
+    label: &'static str,
+    options: &'static [(T, &'static str)],
+    current: T,
+    onchange: Callback<T>,
+) -> Html {
+    let onchange = move |e: Event| {
+        let select: HtmlSelectElement = e.target_unchecked_into();
+        if let Some(&(value, _)) = options.get(select.selected_index() as usize) {
+            onchange.emit(value);
+        }
+    };
+    html! {
+        <label class="flex items-center justify-between gap-2 text-sm">
+            {label}
+            <select class="bg-dark2 rounded-full px-3 py-1" {onchange}>
+                {for options.iter().map(|&(value, name)| html! {
+                    <option selected={value == current}>{name}</option>
+                })}
+            </select>
+        </label>
+    }
+}
+
+/// The text being edited, or a game of its words once the player starts.
+#[function_component(CustomText)]
+pub(crate) fn custom_text(props: &Props) -> Html {
+    let text = use_state(|| LocalStorage::get::<String>(STORAGE_KEY).unwrap_or_default());
+    let options = use_state(TextOptions::default);
+    let playing = use_state(|| false);
+
+    if *playing {
+        let playing = playing.clone();
+        return html! {
+            <>
+                <Game
+                    text={Some(AttrValue::from((*text).clone()))}
+                    text_options={*options}
+                    theme={props.theme}
+                    layout={props.layout}
+                    rules={props.rules}
+                />
+                <button
+                    class="bg-dark2 fixed bottom-4 left-4 rounded-full px-4 py-2 text-sm"
+                    onclick={move |_| playing.set(false)}
+                >
+                    {"Change text"}
+                </button>
+            </>
+        };
+    }
+
+    let oninput = {
+        let text = text.clone();
+        move |e: InputEvent| {
+            let area: HtmlTextAreaElement = e.target_unchecked_into();
+            text.set(area.value());
+        }
+    };
+    let onplay = {
+        let text = text.clone();
+        move |_| {
+            // Not being able to save isn't worth bothering the player about.
+            let _ = LocalStorage::set(STORAGE_KEY, &*text);
+            playing.set(true);
+        }
+    };
+    let set_punctuation = {
+        let options = options.clone();
+        Callback::from(move |punctuation| options.set(options.with_punctuation(punctuation)))
+    };
+    let set_long_words = {
+        let options = options.clone();
+        Callback::from(move |long_words| options.set(options.with_long_words(long_words)))
+    };
+    html! {
+        <div class="flex h-screen w-full flex-col items-center justify-center gap-4 p-4">
+            <textarea
+                class="bg-dark2 h-64 w-full max-w-screen-sm rounded p-2"
+                aria-label="Text to type"
+                placeholder="Paste a paragraph, a quote or some code to type."
+                value={(*text).clone()}
+                {oninput}
+            />
+            <div class="flex w-64 flex-col gap-2">
+                {view_select(
+                    "Punctuation",
+                    &[(Punctuation::Attached, "With words"), (Punctuation::Separate, "On its own")],
+                    options.punctuation,
+                    set_punctuation,
+                )}
+                {view_select(
+                    "Long words",
+                    &[(LongWords::Split, "Split"), (LongWords::Skip, "Skip")],
+                    options.long_words,
+                    set_long_words,
+                )}
+            </div>
+            <button
+                class="bg-primary bg-base rounded-full px-4 py-2 text-sm font-semibold shadow-sm"
+                disabled={text.trim().is_empty()}
+                onclick={onplay}
+            >
+                {"Play"}
+            </button>
+        </div>
+    }
+}
@@ -1,67 +1,306 @@
-mod swatch;
-
-use gloo::{events::EventListener, utils::window};
+use gloo::{
+    events::EventListener,
+    render::{AnimationFrame, request_animation_frame},
+    utils::window,
+};
 use gloo_console::log;
-use gloo_timers::callback::Interval;
-use typetris::game::Event;
 use typetris::game::Game as GameState;
-use typetris::game::board::BoardPosition;
+use typetris::game::daily::Day;
+use typetris::game::difficulty::WordDifficulty;
+use typetris::game::layout::Layout;
+use typetris::game::lesson::{Progress, STAGES};
+use typetris::game::passage::{Passage, TextOptions};
+use typetris::game::power_up::INVENTORY_SIZE;
 use typetris::game::settings::Settings;
+use typetris::game::words::WordSource;
+use typetris::game::{Event, Notice};
 use web_sys::{
-    CanvasRenderingContext2d, HtmlCanvasElement,
-    js_sys::{self},
+    HtmlCanvasElement, HtmlInputElement,
     wasm_bindgen::{JsCast, UnwrapThrowExt},
 };
 use yew::prelude::*;
 
-use swatch::Swatch;
+use super::animation::Animations;
+use super::announcer::Announcer;
+use super::audio;
+use super::canvas::{ResizeWatcher, render_board};
+use super::lessons::{self, view_goal, view_result, view_stages};
+use super::rules::Rules;
+use super::swatch::Swatch;
+use super::text_board::view_text_board;
+use super::theme::Theme;
+use super::touch::{Swipe, TouchControls};
+use super::weak_keys::{self, view_weak_keys};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) enum Msg {
-    Tick,
-    Keydown(KeyboardEvent),
+    /// An animation frame, with its timestamp.
+    Tick(f64),
+    Key(KeyboardEvent),
+    /// The canvas was resized on screen.
+    Resized,
+    /// An event from the on-screen controls.
+    Control(Event),
+    TouchStart(TouchEvent),
+    TouchEnd(TouchEvent),
     NewGame,
+    CopyResult,
+    ToggleTextBoard,
+    /// Play this of the lesson [`STAGES`].
+    SelectStage(usize),
+    /// The page is being closed or hidden.
+    PageHide,
 }
 
+/// How many words can be dropped in practice mode before the key profile is saved again.
+const SAVE_EVERY_WORDS: usize = 5;
+
 #[derive(Debug, Clone, Properties, PartialEq, Eq)]
-pub(crate) struct Props {}
+pub(crate) struct Props {
+    /// Play the daily challenge of this day instead of a random game.
+    #[prop_or_default]
+    pub(crate) daily: Option<Day>,
+    /// Pick words that practise the keys the player misses most, and learn from what they type.
+    #[prop_or_default]
+    pub(crate) practice: bool,
+    /// Play the lesson curriculum, one stage at a time.
+    #[prop_or_default]
+    pub(crate) lessons: bool,
+    /// Type the words of this text in order.
+    #[prop_or_default]
+    pub(crate) text: Option<AttrValue>,
+    /// How [`Self::text`] is cut into words.
+    #[prop_or_default]
+    pub(crate) text_options: TextOptions,
+    /// Where [`Self::text`] is from, if it's a quote.
+    #[prop_or_default]
+    pub(crate) title: Option<AttrValue>,
+    /// Colours to draw the board in.
+    pub(crate) theme: &'static Theme,
+    /// Keyboard layout, for its shortcuts and to break practice statistics down by finger and row.
+    pub(crate) layout: Layout,
+    /// Optional rules to play by, unless playing the daily challenge or lessons.
+    #[prop_or_default]
+    pub(crate) rules: Rules,
+}
+
+impl Props {
+    fn settings(&self) -> Settings {
+        match self.daily {
+            Some(day) => day.settings(),
+            None if self.practice || self.text.is_some() => self.rules.apply(Settings::default()),
+            None => self.rules.apply(
+                Settings::default()
+                    .with_starts_with_splash(true)
+                    .with_word_difficulty(WordDifficulty::ByLevel),
+            ),
+        }
+    }
+
+    /// A new game, of `stage` if playing lessons.
+    fn game(&self, stage: usize) -> GameState {
+        if self.lessons {
+            return STAGES[stage].game(self.layout);
+        }
+        let settings = self.settings();
+        let words = if let Some(text) = &self.text {
+            WordSource::Text(Passage::new(text, settings.width, self.text_options))
+        } else if self.practice {
+            WordSource::Practice(weak_keys::load())
+        } else {
+            WordSource::Random
+        };
+        GameState::with_words(settings, words)
+    }
+}
 
 pub(crate) struct Game {
-    _tick_handle: Interval,
-    _listener: EventListener,
+    _frame_handle: AnimationFrame,
+    _key_listeners: [EventListener; 2],
+    _page_hide_listener: EventListener,
     state: GameState,
+    animations: Animations,
+    announcer: Announcer,
+    progress: Progress,
+    /// The lesson stage being played, if playing lessons.
+    stage: usize,
+    /// Whether to show the board as a table of letters as well.
+    text_board: bool,
     canvas_node: NodeRef,
-    last_timestamp: f64,
+    resize_watcher: ResizeWatcher,
+    /// The hidden text field that brings up the soft keyboard.
+    input_node: NodeRef,
+    swipe: Swipe,
+    /// Timestamp of the last frame, or `None` if time should start counting from the next one.
+    last_timestamp: Option<f64>,
     swatch: Swatch,
+    /// Words dropped since the key profile was last saved.
+    unsaved_words: usize,
+}
+
+/// Listen for key presses and releases anywhere in the window.
+pub(super) fn key_listeners(callback: Callback<KeyboardEvent>) -> [EventListener; 2] {
+    ["keydown", "keyup"].map(|event_type| {
+        let callback = callback.clone();
+        EventListener::new(&window(), event_type, move |e| {
+            callback.emit(e.clone().dyn_into().unwrap_throw())
+        })
+    })
+}
+
+/// Call `callback` with the timestamp of the next animation frame.
+pub(super) fn request_frame(callback: Callback<f64>) -> AnimationFrame {
+    request_animation_frame(move |timestamp| callback.emit(timestamp))
+}
+
+/// Time since the frame before, or zero if there wasn't one, and remember `timestamp` for the
+/// next frame.
+pub(super) fn frame_delta(last_timestamp: &mut Option<f64>, timestamp: f64) -> f64 {
+    let delta_time = last_timestamp.map_or(0.0, |last| timestamp - last);
+    *last_timestamp = Some(timestamp);
+    delta_time
+}
+
+/// Map a key press or release to the game event it stands for, if any.
+pub(super) fn key_to_event(event: &KeyboardEvent, layout: Layout) -> Option<Event> {
+    if event.type_() == "keyup" {
+        return match event.key().as_str() {
+            "ArrowLeft" => Some(Event::LeftReleased),
+            "ArrowRight" => Some(Event::RightReleased),
+            _ => None,
+        };
+    }
+    let [left, rotate, right] = layout.shortcuts().map(String::from);
+    Some(match event.key().as_str() {
+        "Enter" | "Tab" | " " => Event::Next,
+        // Held arrows are repeated by the game, not the keyboard.
+        "ArrowLeft" | "ArrowRight" if event.repeat() => return None,
+        "ArrowLeft" => Event::LeftPressed,
+        key if event.ctrl_key() && key == left => Event::Left,
+        "ArrowRight" => Event::RightPressed,
+        key if event.ctrl_key() && key == right => Event::Right,
+        "ArrowUp" => Event::Rotate,
+        key if event.ctrl_key() && key == rotate => Event::Rotate,
+        "Backspace" => Event::Delete,
+        // Only digits with a power-up slot use one. The rest are typed like any other character.
+        key if key.len() == 1
+            && key
+                .parse::<usize>()
+                .is_ok_and(|slot| (1..=INVENTORY_SIZE).contains(&slot)) =>
+        {
+            Event::UsePowerUp(key.parse::<usize>().unwrap() - 1)
+        }
+        key if key.len() == 1 && key.is_ascii() => Event::Type(key.chars().next().unwrap()),
+        _ => return None,
+    })
 }
 
 impl Game {
-    fn tick(&mut self) -> bool {
-        let timestamp = js_sys::Date::new_0().value_of();
-        let delta_time = timestamp - self.last_timestamp;
-        self.last_timestamp = timestamp;
+    fn view_power_ups(&self) -> Html {
+        let power_ups = self.state.power_ups();
+        html! {
+            <div class="mt-4 flex flex-col items-center gap-2">
+                <p class="text-light3 text-sm">
+                    {format!("Flawless streak: {}", power_ups.streak())}
+                    if power_ups.slow_time() > 0.0 {
+                        <span class="text-primary">{format!(" · slow time {:.0}s", power_ups.slow_time() / 1_000.0)}</span>
+                    }
+                </p>
+                <div class="flex flex-row gap-2">
+                    {for (0..INVENTORY_SIZE).map(|i| html! {
+                        <div class="bg-dark2 min-w-28 rounded px-2 py-1 text-center text-sm">
+                            <span class="text-primary">{i + 1}</span>{" "}
+                            {power_ups.inventory().get(i).map_or("—", |p| p.name())}
+                        </div>
+                    })}
+                </div>
+            </div>
+        }
+    }
 
-        self.state
-            .handle_event(typetris::game::Event::Tick(delta_time))
+    fn tick(&mut self, ctx: &Context<Self>, timestamp: f64) -> bool {
+        self._frame_handle = request_frame(ctx.link().callback(Msg::Tick));
+        let delta_time = frame_delta(&mut self.last_timestamp, timestamp);
+        let ret = self.state.handle_event(Event::Tick(delta_time));
+        self.animations.tick(delta_time);
+        let notices = self.state.take_notices();
+        self.unsaved_words += notices.iter().filter(|&n| *n == Notice::Dropped).count();
+        if notices.contains(&Notice::GameOver) || self.unsaved_words >= SAVE_EVERY_WORDS {
+            self.save_profile();
+        }
+        if ctx.props().lessons
+            && notices.contains(&Notice::Finished)
+            && STAGES[self.stage].passed(&self.state.stats())
+        {
+            self.progress.record_pass(ctx.props().layout, self.stage);
+            lessons::save(&self.progress);
+        }
+        audio::play(&notices);
+        let announced = self.announcer.update(&self.state, &notices);
+        self.animations.extend(notices);
+        // Falling and sliding blocks are drawn between cells, so they move on every frame.
+        ret || announced || self.state.is_playing() || self.animations.is_playing()
     }
 
-    fn keydown(&mut self, event: KeyboardEvent) -> bool {
-        let event = match event.key().as_str() {
-            "Enter" | "Tab" | " " => Event::Next,
-            "ArrowLeft" => Event::Left,
-            "h" if event.ctrl_key() => Event::Left,
-            "ArrowRight" => Event::Right,
-            "l" if event.ctrl_key() => Event::Right,
-            "Backspace" => Event::Delete,
-            key if key.len() == 1 && key.is_ascii() => Event::Type(key.chars().next().unwrap()),
-            _ => return false,
+    fn key(&mut self, ctx: &Context<Self>, event: KeyboardEvent) -> bool {
+        let Some(event) = key_to_event(&event, ctx.props().layout) else {
+            return false;
         };
         self.state.handle_event(event)
     }
 
+    /// Swipes on the board move the focused block, and taps bring up the soft keyboard.
+    fn touch_end(&mut self, event: TouchEvent) -> bool {
+        let canvas: HtmlCanvasElement = self.canvas_node.cast().unwrap();
+        let cell_width =
+            canvas.get_bounding_client_rect().width() / self.state.board().width() as f64;
+        let Some(events) = self.swipe.end(&event, cell_width) else {
+            if let Some(input) = self.input_node.cast::<HtmlInputElement>() {
+                let _ = input.focus();
+            }
+            return false;
+        };
+        event.prevent_default();
+        let mut ret = false;
+        for event in events {
+            ret |= self.state.handle_event(event);
+        }
+        ret
+    }
+
     fn new_game(&mut self) -> bool {
-        self.last_timestamp = js_sys::Date::new_0().value_of();
-        self.state.handle_event(Event::NewGame)
+        self.last_timestamp = None;
+        self.animations = Animations::default();
+        let ret = self.state.handle_event(Event::NewGame);
+        self.save_profile();
+        ret
+    }
+
+    /// Remember what the player got wrong in practice mode for next time.
+    fn save_profile(&mut self) {
+        self.unsaved_words = 0;
+        if let Some(profile) = self.state.words().key_profile() {
+            weak_keys::save(profile);
+        }
+    }
+
+    fn select_stage(&mut self, ctx: &Context<Self>, stage: usize) -> bool {
+        self.stage = stage;
+        self.state = ctx.props().game(stage);
+        self.animations = Animations::default();
+        self.last_timestamp = None;
+        true
+    }
+
+    fn copy_result(&self, ctx: &Context<Self>) -> bool {
+        if let Some(day) = ctx.props().daily {
+            // Nothing useful to do if the browser refuses.
+            let _ = window()
+                .navigator()
+                .clipboard()
+                .write_text(&day.result_card(&self.state));
+        }
+        false
     }
 }
 
@@ -70,24 +309,53 @@ impl Component for Game {
     type Properties = Props;
 
     fn create(ctx: &Context<Self>) -> Self {
-        let link = ctx.link().clone();
-        let _tick_handle = Interval::new(1_000 / 30, move || link.send_message(Msg::Tick));
-        let timestamp = js_sys::Date::new_0().value_of();
+        let _frame_handle = request_frame(ctx.link().callback(Msg::Tick));
         let callback = ctx.link().callback(|e: KeyboardEvent| {
-            log!("keydown", &e);
-            Msg::Keydown(e)
-        });
-        let _listener = EventListener::new(&window(), "keydown", move |e| {
-            callback.emit(e.clone().dyn_into().unwrap_throw())
+            log!("key", &e);
+            Msg::Key(e)
         });
+        let _key_listeners = key_listeners(callback);
+        let callback = ctx.link().callback(|_| Msg::PageHide);
+        let _page_hide_listener =
+            EventListener::new(&window(), "pagehide", move |e| callback.emit(e.clone()));
+        let progress = lessons::load();
+        let stage = progress.current_stage(ctx.props().layout);
         Self {
-            _tick_handle,
-            _listener,
-            state: GameState::new(Settings::default().with_starts_with_splash(true)),
+            _frame_handle,
+            _key_listeners,
+            _page_hide_listener,
+            state: ctx.props().game(stage),
+            animations: Animations::default(),
+            announcer: Announcer::default(),
+            progress,
+            stage,
+            text_board: false,
             canvas_node: NodeRef::default(),
-            last_timestamp: timestamp,
-            swatch: Swatch::new(),
+            resize_watcher: ResizeWatcher::new(ctx.link().callback(|_| Msg::Resized)),
+            input_node: NodeRef::default(),
+            swipe: Swipe::default(),
+            last_timestamp: None,
+            swatch: Swatch::from(ctx.props().theme),
+            unsaved_words: 0,
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        let props = ctx.props();
+        if props.daily != old_props.daily
+            || props.practice != old_props.practice
+            || props.lessons != old_props.lessons
+            || props.text != old_props.text
+            || props.text_options != old_props.text_options
+            || (props.daily.is_none() && !props.lessons && props.rules != old_props.rules)
+            // Lessons are made of the letters on the rows of the layout.
+            || (props.lessons && props.layout != old_props.layout)
+        {
+            self.save_profile();
+            self.select_stage(ctx, self.progress.current_stage(props.layout));
         }
+        self.swatch = Swatch::from(ctx.props().theme);
+        true
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
@@ -103,11 +371,16 @@ impl Component for Game {
                     class="flex h-screen w-full flex-col items-center justify-center gap-8 overflow-hidden p-4 lg:flex-row"
                 >
                     <canvas
-                        class="w-full max-w-screen-sm lg:h-full lg:w-auto lg:max-w-none"
+                        class="w-full max-w-screen-sm touch-none lg:h-full lg:w-auto lg:max-w-none"
                         style={style}
+                        role="img"
+                        aria-label="Game board. Turn on the text board to read it."
                         ref={self.canvas_node.clone()}
+                        ontouchstart={ctx.link().callback(Msg::TouchStart)}
+                        ontouchend={ctx.link().callback(Msg::TouchEnd)}
                     />
                     <div class="flex flex-col items-center justify-center">
+                        <p class="sr-only" role="status" aria-live="polite">{self.announcer.text()}</p>
                         if self.state.is_splash(){
                             <p>{"You have to type each word before you can move it."}</p>
                             <p>{"Line up and fill each row to clear it and score."}</p>
@@ -130,12 +403,80 @@ impl Component for Game {
                                 {"Play"}
                             </button>
                         } else {
+                            if let Some(day) = ctx.props().daily {
+                                <p class="text-light2">{format!("Daily challenge {day}")}</p>
+                            }
+                            if ctx.props().lessons {
+                                if self.state.is_finished() {
+                                    {view_result(&STAGES[self.stage], &self.state.stats())}
+                                }
+                                {view_goal(&STAGES[self.stage], &self.state.stats())}
+                            }
+                            if let WordSource::Text(passage) = self.state.words() {
+                                if self.state.is_finished() {
+                                    <h1 class="text-success text-6xl font-bold">{"Done!"}</h1>
+                                }
+                                if self.state.is_finished() || self.state.is_game_over() {
+                                    <p class="text-light2">
+                                        {format!("You typed {:.0}% of ", passage.typed_fraction() * 100.0)}
+                                        if let Some(title) = &ctx.props().title {
+                                            <em>{title}</em>
+                                        } else {
+                                            {"your text"}
+                                        }
+                                    </p>
+                                } else {
+                                    <p class="text-light3 text-sm">
+                                        {format!("{} of {} words", passage.spawned(), passage.words().len())}
+                                    </p>
+                                }
+                                if let (true, Some(text)) = (self.state.is_finished(), &ctx.props().text) {
+                                    <blockquote class="text-light3 mt-2 max-w-md text-sm">{text}</blockquote>
+                                }
+                            }
                             if self.state.is_game_over() {
                                 <h1 class="text-error text-8xl font-bold">{"Game Over"}</h1>
                             }
                             <h1 class="text-light1 text-6xl font-bold">{"Score:"}</h1>
                             <h2 class="text-light2 text-4xl">{self.state.score()}</h2>
+                            <p class="text-light3">{format!("Level {}", self.state.level())}</p>
+                            {self.view_power_ups()}
+                            if let Some(profile) = self.state.words().key_profile() {
+                                {view_weak_keys(profile, ctx.props().layout)}
+                            }
+                            <TouchControls
+                                layout={ctx.props().layout}
+                                onevent={ctx.link().callback(Msg::Control)}
+                                input={self.input_node.clone()}
+                            />
+                            if ctx.props().lessons {
+                                {view_stages(
+                                    &self.progress,
+                                    ctx.props().layout,
+                                    self.stage,
+                                    ctx.link().callback(Msg::SelectStage),
+                                )}
+                            }
+                            if let (Some(day), true) = (ctx.props().daily, self.state.is_game_over()) {
+                                <pre class="mt-4 text-base leading-tight">{day.result_card(&self.state)}</pre>
+                                <button
+                                    class="bg-primary bg-base mt-4 max-w-fit rounded-full px-4 py-2 text-sm font-semibold shadow-sm"
+                                    onclick={ctx.link().callback(|_| Msg::CopyResult)}
+                                >
+                                    {"Copy result"}
+                                </button>
+                            }
                             <button class="font-semibold text-sm bg-primary rounded-full shadow-sm px-4 py-2 mt-4 max-w-fit bg-base" onclick={new_game_onclick}>{"Restart"}</button>
+                            <button
+                                class="bg-dark2 mt-4 max-w-fit rounded-full px-4 py-2 text-sm"
+                                aria-pressed={self.text_board.to_string()}
+                                onclick={ctx.link().callback(|_| Msg::ToggleTextBoard)}
+                            >
+                                {"Text board"}
+                            </button>
+                            if self.text_board {
+                                <div class="mt-4">{view_text_board(self.state.board())}</div>
+                            }
                         }
                     </div>
                 </div>
@@ -145,125 +486,48 @@ impl Component for Game {
 
     fn rendered(&mut self, _ctx: &Context<Self>, first_render: bool) {
         let canvas: HtmlCanvasElement = self.canvas_node.cast().unwrap();
-        canvas.focus().unwrap();
-        let context: CanvasRenderingContext2d = canvas
-            .get_context("2d")
-            .unwrap()
-            .unwrap()
-            .dyn_into()
-            .unwrap();
-
-        let (canvas_height, canvas_width) = if first_render {
-            let rect = canvas.get_bounding_client_rect();
-            let window = web_sys::window().unwrap();
-            self.swatch
-                .extract(window.get_computed_style(&canvas).unwrap().unwrap());
-            let dpr = window.device_pixel_ratio();
-            let canvas_height = rect.height() * dpr;
-            let canvas_width = rect.width() * dpr;
-            canvas.set_height(canvas_height as _);
-            canvas.set_width(canvas_width as _);
-            (canvas_height, canvas_width)
-        } else {
-            (canvas.height() as f64, canvas.width() as f64)
-        };
-
-        let cell_width = canvas_width / self.state.board().width() as f64;
-        let cell_height = canvas_height / self.state.board().height() as f64;
-
-        context.set_fill_style_str(&self.swatch.bg_color);
-        context.fill_rect(0.0, 0.0, canvas_width, canvas_height);
-        context.set_font(format!("normal {:.0}px system-ui", cell_width * 0.7).as_str());
-        context.set_text_align("center");
-        context.set_text_baseline("middle");
-        context.set_line_width(5.0);
-
-        for (index, block) in self.state.board().blocks().iter().enumerate() {
-            let pos = block.position();
-            let x = pos.x as f64 * cell_width;
-            let y = pos.y as f64 * cell_height;
-            let width = block.width() as f64 * cell_width;
-
-            context.begin_path();
-            context.rect(x, y, width, cell_height);
-            context.set_fill_style_str(if block.is_interactable() {
-                &self.swatch.regular_block_color
-            } else {
-                &self.swatch.disabled_block_color
-            });
-            context.fill();
-
-            if self.state.board().get_focused_index() == Some(index) {
-                for (i, (a, b)) in block
-                    .input_text()
-                    .chars()
-                    .zip(block.assigned_text().chars())
-                    .enumerate()
-                {
-                    let x = x + i as f64 * cell_width;
-                    let color = if a == b {
-                        &self.swatch.success_color
-                    } else {
-                        &self.swatch.error_color
-                    };
-                    context.set_fill_style_str(color);
-                    context.fill_rect(x, y, cell_width, cell_height);
-                }
-            }
+        // Only on the first render, so that it doesn't take focus from the soft keyboard's field.
+        if first_render {
+            canvas.focus().unwrap();
+            self.resize_watcher.observe(&self.canvas_node);
+        }
+        let fit = self.resize_watcher.take_stale() || first_render;
+        self.animations.follow(&self.state);
+        render_board(
+            &self.canvas_node,
+            &self.state,
+            &self.animations,
+            &self.swatch,
+            fit,
+        );
+    }
 
-            context.set_fill_style_str("white");
-            for i in 0..block.assigned_text().len() {
-                let x = x + i as f64 * cell_width + cell_width / 2.0;
-                let y = y + cell_height / 2.0;
-                context
-                    .fill_text(&block.assigned_text()[i..i + 1], x, y)
-                    .unwrap();
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Tick(timestamp) => self.tick(ctx, timestamp),
+            Msg::Key(e) => self.key(ctx, e),
+            Msg::Resized => true,
+            Msg::Control(event) => self.state.handle_event(event),
+            Msg::TouchStart(e) => {
+                self.swipe.start(&e);
+                false
             }
-
-            for i in 1..block.width() {
-                let x = i as f64 * cell_width + x;
-                context.move_to(x, y);
-                context.line_to(x, y + cell_height);
+            Msg::TouchEnd(e) => self.touch_end(e),
+            Msg::NewGame => self.new_game(),
+            Msg::CopyResult => self.copy_result(ctx),
+            Msg::SelectStage(stage) => self.select_stage(ctx, stage),
+            Msg::ToggleTextBoard => {
+                self.text_board = !self.text_board;
+                true
             }
-            context.set_stroke_style_str("black");
-            context.stroke();
-
-            if let Some(focus) = self.state.board().get_focused() {
-                let n = focus.input_text().len();
-                if n < focus.assigned_text().len() {
-                    let BoardPosition { x, y } = focus.position();
-                    let x = x as f64 * cell_width + n as f64 * cell_width;
-                    let y = y as f64 * cell_height;
-                    context.begin_path();
-                    context
-                        .arc(
-                            x + cell_width * 0.5,
-                            y + cell_height * 0.5,
-                            cell_width.min(cell_height) * 0.4,
-                            0.0,
-                            std::f64::consts::PI * 2.0,
-                        )
-                        .unwrap();
-                    context.move_to(x + cell_width * 0.1, y + cell_height * 0.1);
-                    context.line_to(x + cell_width * 0.2, y + cell_height * 0.2);
-                    context.move_to(x + cell_width * 0.1, y + cell_height * 0.9);
-                    context.line_to(x + cell_width * 0.2, y + cell_height * 0.8);
-                    context.move_to(x + cell_width * 0.9, y + cell_height * 0.1);
-                    context.line_to(x + cell_width * 0.8, y + cell_height * 0.2);
-                    context.move_to(x + cell_width * 0.9, y + cell_height * 0.9);
-                    context.line_to(x + cell_width * 0.8, y + cell_height * 0.8);
-                    context.set_stroke_style_str(&self.swatch.reticle_color);
-                    context.stroke();
-                }
+            Msg::PageHide => {
+                self.save_profile();
+                false
             }
         }
     }
 
-    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
-        match msg {
-            Msg::Tick => self.tick(),
-            Msg::Keydown(e) => self.keydown(e),
-            Msg::NewGame => self.new_game(),
-        }
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        self.save_profile();
     }
 }
@@ -0,0 +1,87 @@
+//! Progress through the lesson curriculum: remembered between visits, and shown as a list of
+//! stages to pick from.
+
+use gloo::storage::{LocalStorage, Storage};
+use typetris::game::layout::Layout;
+use typetris::game::lesson::{Progress, STAGES, Stage};
+use typetris::game::stats::Stats;
+use yew::prelude::*;
+
+const STORAGE_KEY: &str = "typetris.lessons";
+
+/// The progress saved on an earlier visit, or none.
+pub(super) fn load() -> Progress {
+    LocalStorage::get(STORAGE_KEY).unwrap_or_default()
+}
+
+pub(super) fn save(progress: &Progress) {
+    // Not being able to save isn't worth bothering the player about.
+    let _ = LocalStorage::set(STORAGE_KEY, progress);
+}
+
+/// What it takes to pass `stage`.
+pub(super) fn view_goal(stage: &Stage, stats: &Stats) -> Html {
+    html! {
+        <>
+            <p class="text-light2">{format!("Lesson: {}", stage.name)}</p>
+            <p class="text-light3 text-sm">
+                {format!(
+                    "{} of {} words · pass with {:.0}% accuracy and {:.0} WPM",
+                    stats.words(),
+                    stage.words,
+                    stage.min_accuracy * 100.0,
+                    stage.min_wpm,
+                )}
+            </p>
+        </>
+    }
+}
+
+/// How a finished game of `stage` went.
+pub(super) fn view_result(stage: &Stage, stats: &Stats) -> Html {
+    let (class, verdict) = if stage.passed(stats) {
+        ("text-success", "Passed!")
+    } else {
+        ("text-error", "Not yet")
+    };
+    html! {
+        <>
+            <h1 class={classes!(class, "text-6xl", "font-bold")}>{verdict}</h1>
+            <p class="text-light2">
+                {format!("{:.0}% accuracy, {:.0} WPM", stats.accuracy() * 100.0, stats.wpm())}
+            </p>
+        </>
+    }
+}
+
+/// A button for every stage, with the ones not unlocked yet on `layout` disabled.
+pub(super) fn view_stages(
+    progress: &Progress,
+    layout: Layout,
+    current: usize,
+    onselect: Callback<usize>,
+) -> Html {
+    html! {
+        <div class="mt-4 flex flex-col items-center gap-2 text-sm">
+            {for STAGES.iter().enumerate().map(|(i, stage)| {
+                let onselect = onselect.clone();
+                let passed = i < progress.stages_passed(layout);
+                let class = classes!(
+                    "rounded-full",
+                    "px-3",
+                    "py-1",
+                    if i == current { "bg-primary" } else { "bg-dark2" },
+                );
+                html! {
+                    <button
+                        {class}
+                        disabled={!progress.is_unlocked(layout, i)}
+                        onclick={move |_| onselect.emit(i)}
+                    >
+                        {format!("{}. {}{}", i + 1, stage.name, if passed { " ✓" } else { "" })}
+                    </button>
+                }
+            })}
+        </div>
+    }
+}
@@ -1,12 +1,235 @@
+mod animation;
+mod announcer;
+mod audio;
+mod canvas;
+mod custom_text;
 mod game;
+mod lessons;
+mod online;
+mod quotes;
+mod rules;
+mod swatch;
+mod text_board;
+mod theme;
+mod touch;
+mod versus;
+mod weak_keys;
 
+use gloo::storage::{LocalStorage, Storage};
+use typetris::game::daily::Day;
+use typetris::game::layout::Layout;
+use web_sys::{HtmlInputElement, HtmlSelectElement, js_sys};
 use yew::prelude::*;
 
+use audio::AudioSettings;
+use custom_text::CustomText;
 use game::Game;
+use online::Online;
+use quotes::Quotes;
+use rules::Rules;
+use theme::{THEMES, Theme};
+use versus::{Opponent, Versus};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Solo,
+    Daily,
+    /// Solo, with words that practise the keys the player misses most.
+    Practice,
+    Lessons,
+    /// Solo, typing a text the player pasted in.
+    Text,
+    /// Solo, typing a passage from a book or speech.
+    Quotes,
+    Versus(Opponent),
+    Online,
+}
+
+/// Today's date in the player's time zone.
+fn today() -> Day {
+    let now = js_sys::Date::new_0();
+    Day::new(
+        now.get_full_year() as i32,
+        now.get_month() as u8 + 1,
+        now.get_date() as u8,
+    )
+}
+
+const LAYOUT_STORAGE_KEY: &str = "typetris.layout";
+
+/// The keyboard layout chosen on an earlier visit, or QWERTY.
+fn load_layout() -> Layout {
+    LocalStorage::get(LAYOUT_STORAGE_KEY).unwrap_or_default()
+}
+
+/// Mute button and volume slider.
+#[function_component(AudioControls)]
+fn audio_controls() -> Html {
+    let settings = use_state(AudioSettings::load);
+    let update = {
+        let settings = settings.clone();
+        move |new: AudioSettings| {
+            audio::set_settings(new);
+            settings.set(new);
+        }
+    };
+    let toggle = {
+        let update = update.clone();
+        let current = *settings;
+        move |_| {
+            update(AudioSettings {
+                muted: !current.muted,
+                ..current
+            })
+        }
+    };
+    // Turning the volume up or down unmutes too.
+    let oninput = move |e: InputEvent| {
+        let input: HtmlInputElement = e.target_unchecked_into();
+        update(AudioSettings {
+            volume: input.value_as_number() / 100.0,
+            muted: false,
+        })
+    };
+    html! {
+        <div class="flex items-center gap-2 px-3 py-1">
+            <button onclick={toggle}>{if settings.muted { "Unmute" } else { "Mute" }}</button>
+            <input
+                type="range"
+                min="0"
+                max="100"
+                aria-label="Volume"
+                value={(settings.volume * 100.0).round().to_string()}
+                {oninput}
+            />
+        </div>
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+struct RuleControlsProps {
+    rules: Rules,
+    onchange: Callback<Rules>,
+}
+
+/// Checkboxes for the optional [`Rules`].
+#[function_component(RuleControls)]
+fn rule_controls(props: &RuleControlsProps) -> Html {
+    let checkbox = |label: &'static str, checked: bool, set: fn(&mut Rules, bool)| {
+        let rules = props.rules;
+        let onchange = props.onchange.clone();
+        let onchange = move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let mut new = rules;
+            set(&mut new, input.checked());
+            onchange.emit(new);
+        };
+        html! {
+            <label class="flex items-center gap-1">
+                <input type="checkbox" {checked} {onchange} />
+                {label}
+            </label>
+        }
+    };
+    html! {
+        <div class="flex items-center gap-2 px-3 py-1">
+            {checkbox("Shapes", props.rules.shaped_pieces, |r, on| r.shaped_pieces = on)}
+            {checkbox("Specials", props.rules.special_blocks, |r, on| r.special_blocks = on)}
+        </div>
+    }
+}
 
 #[function_component(App)]
 pub fn app() -> Html {
+    let mode = use_state(|| Mode::Solo);
+    let theme = use_state(|| {
+        let theme = Theme::load();
+        theme.apply();
+        theme
+    });
+    let onchange = {
+        let theme = theme.clone();
+        move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            if let Some(new) = Theme::get(&select.value()) {
+                new.apply();
+                theme.set(new);
+            }
+        }
+    };
+    let layout = use_state(load_layout);
+    let onchange_layout = {
+        let layout = layout.clone();
+        move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            if let Some(&new) = Layout::ALL.get(select.selected_index() as usize) {
+                // Not being able to save isn't worth bothering the player about.
+                let _ = LocalStorage::set(LAYOUT_STORAGE_KEY, new);
+                layout.set(new);
+            }
+        }
+    };
+    let rules = use_state(Rules::load);
+    let onchange_rules = {
+        let rules = rules.clone();
+        Callback::from(move |new: Rules| {
+            new.save();
+            rules.set(new);
+        })
+    };
+    let button = |label: &'static str, target: Mode| {
+        let mode = mode.clone();
+        let class = classes!(
+            "rounded-full",
+            "px-3",
+            "py-1",
+            (*mode == target).then_some("bg-primary")
+        );
+        html! {
+            <button {class} onclick={move |_| mode.set(target)}>{label}</button>
+        }
+    };
     html! {
-        <Game />
+        <>
+            <nav class="fixed inset-x-0 top-0 flex justify-center gap-2 p-2 text-sm">
+                {button("Solo", Mode::Solo)}
+                {button("Daily", Mode::Daily)}
+                {button("Practice", Mode::Practice)}
+                {button("Lessons", Mode::Lessons)}
+                {button("Text", Mode::Text)}
+                {button("Quotes", Mode::Quotes)}
+                {button("Versus bot", Mode::Versus(Opponent::Bot))}
+                {button("Hot-seat", Mode::Versus(Opponent::HotSeat))}
+                {button("Online", Mode::Online)}
+                <AudioControls />
+                <RuleControls rules={*rules} onchange={onchange_rules} />
+                <select class="bg-dark2 rounded-full px-3 py-1" aria-label="Theme" {onchange}>
+                    {for THEMES.iter().map(|t| html! {
+                        <option value={t.id} selected={t.id == theme.id}>{t.name}</option>
+                    })}
+                </select>
+                <select
+                    class="bg-dark2 rounded-full px-3 py-1"
+                    aria-label="Keyboard layout"
+                    onchange={onchange_layout}
+                >
+                    {for Layout::ALL.iter().map(|&l| html! {
+                        <option selected={l == *layout}>{l.name()}</option>
+                    })}
+                </select>
+            </nav>
+            {match *mode {
+                Mode::Solo => html! { <Game theme={*theme} layout={*layout} rules={*rules} /> },
+                Mode::Daily => html! { <Game daily={Some(today())} theme={*theme} layout={*layout} /> },
+                Mode::Practice => html! {
+                    <Game practice=true theme={*theme} layout={*layout} rules={*rules} />
+                },
+                Mode::Lessons => html! { <Game lessons=true theme={*theme} layout={*layout} /> },
+                Mode::Text => html! { <CustomText theme={*theme} layout={*layout} rules={*rules} /> },
+                Mode::Quotes => html! { <Quotes theme={*theme} layout={*layout} rules={*rules} /> },
+                Mode::Versus(opponent) => html! { <Versus {opponent} theme={*theme} layout={*layout} /> },
+                Mode::Online => html! { <Online theme={*theme} layout={*layout} /> },
+            }}
+        </>
     }
 }
@@ -0,0 +1,367 @@
+use gloo::{events::EventListener, render::AnimationFrame};
+use typetris::game::Event;
+use typetris::game::layout::Layout;
+use typetris::game::online::OnlineMatch;
+use typetris::game::settings::Settings;
+use typetris::game::versus::Player;
+use typetris::protocol::{DEFAULT_PORT, Message};
+use web_sys::{
+    HtmlInputElement, MessageEvent, WebSocket,
+    wasm_bindgen::{JsCast, UnwrapThrowExt},
+};
+use yew::prelude::*;
+
+use super::animation::Animations;
+use super::audio;
+use super::canvas::{ResizeWatcher, render_board};
+use super::game::{frame_delta, key_listeners, key_to_event, request_frame};
+use super::swatch::Swatch;
+use super::theme::Theme;
+use super::touch::TouchControls;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Msg {
+    /// An animation frame, with its timestamp.
+    Tick(f64),
+    Key(KeyboardEvent),
+    /// A canvas was resized on screen.
+    Resized,
+    /// An event from the on-screen controls.
+    Control(Event),
+    Connect,
+    Opened,
+    Received(String),
+    Closed,
+}
+
+#[derive(Debug, Clone, Properties, PartialEq, Eq)]
+pub(crate) struct Props {
+    /// Colours to draw the boards in.
+    pub(crate) theme: &'static Theme,
+    /// Keyboard layout, for its shortcuts.
+    pub(crate) layout: Layout,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Idle,
+    Connecting,
+    /// Connected and waiting for an opponent to join the room.
+    Waiting,
+    Playing,
+    Disconnected,
+}
+
+struct Connection {
+    socket: WebSocket,
+    _listeners: [EventListener; 3],
+}
+
+pub(crate) struct Online {
+    _frame_handle: AnimationFrame,
+    _key_listeners: [EventListener; 2],
+    connection: Option<Connection>,
+    status: Status,
+    state: Option<OnlineMatch>,
+    url_node: NodeRef,
+    room_node: NodeRef,
+    canvas_nodes: [NodeRef; 2],
+    resize_watcher: ResizeWatcher,
+    /// The hidden text field that brings up the soft keyboard.
+    input_node: NodeRef,
+    animations: [Animations; 2],
+    /// Whether the canvases of the current match have been sized yet.
+    canvases_sized: bool,
+    last_timestamp: Option<f64>,
+    swatch: Swatch,
+}
+
+impl Online {
+    fn connect(&mut self, ctx: &Context<Self>) -> bool {
+        let url = self.url_node.cast::<HtmlInputElement>().unwrap().value();
+        let Ok(socket) = WebSocket::new(&url) else {
+            self.status = Status::Disconnected;
+            return true;
+        };
+        let link = ctx.link();
+        let opened = link.callback(|_| Msg::Opened);
+        let received = link.callback(Msg::Received);
+        let closed = link.callback(|_| Msg::Closed);
+        let _listeners = [
+            EventListener::new(&socket, "open", move |_| opened.emit(())),
+            EventListener::new(&socket, "message", move |e| {
+                let e: &MessageEvent = e.dyn_ref().unwrap_throw();
+                if let Some(text) = e.data().as_string() {
+                    received.emit(text);
+                }
+            }),
+            EventListener::new(&socket, "close", move |_| closed.emit(())),
+        ];
+        self.connection = Some(Connection { socket, _listeners });
+        self.status = Status::Connecting;
+        self.state = None;
+        true
+    }
+
+    fn opened(&mut self) -> bool {
+        let room = self.room_node.cast::<HtmlInputElement>().unwrap().value();
+        self.send(&Message::Join { room });
+        self.status = Status::Waiting;
+        true
+    }
+
+    fn received(&mut self, text: String) -> bool {
+        let Ok(message) = Message::from_json(&text) else {
+            return false;
+        };
+        let ret = match (message, &mut self.state) {
+            (Message::Start { seed, player }, _) => {
+                self.state = Some(OnlineMatch::new(Settings::default(), seed, player));
+                self.status = Status::Playing;
+                self.canvases_sized = false;
+                self.animations = Default::default();
+                self.last_timestamp = None;
+                true
+            }
+            (message, Some(state)) => state.handle_message(message),
+            (_, None) => false,
+        };
+        self.flush();
+        ret
+    }
+
+    fn closed(&mut self) -> bool {
+        self.connection = None;
+        if self.state.as_ref().is_none_or(|s| !s.is_over()) {
+            self.status = Status::Disconnected;
+        }
+        true
+    }
+
+    fn tick(&mut self, ctx: &Context<Self>, timestamp: f64) -> bool {
+        self._frame_handle = request_frame(ctx.link().callback(Msg::Tick));
+        let delta_time = frame_delta(&mut self.last_timestamp, timestamp);
+
+        let Some(state) = &mut self.state else {
+            return false;
+        };
+        let ret = state.handle_event(Event::Tick(delta_time));
+        for (animations, player) in self.animations.iter_mut().zip([Player::One, Player::Two]) {
+            animations.tick(delta_time);
+            let notices = state.take_notices(player);
+            if player == state.player() {
+                audio::play(&notices);
+            }
+            animations.extend(notices);
+        }
+        // Falling and sliding blocks are drawn between cells, so they move on every frame.
+        let ret = ret || !state.is_over() || self.animations.iter().any(Animations::is_playing);
+        self.flush();
+        ret
+    }
+
+    fn key(&mut self, ctx: &Context<Self>, event: KeyboardEvent) -> bool {
+        let Some(event) = key_to_event(&event, ctx.props().layout) else {
+            return false;
+        };
+        self.play(event)
+    }
+
+    fn play(&mut self, event: Event) -> bool {
+        let Some(state) = &mut self.state else {
+            return false;
+        };
+        let ret = state.handle_event(event);
+        self.flush();
+        ret
+    }
+
+    fn flush(&mut self) {
+        let Some(state) = &mut self.state else {
+            return;
+        };
+        for message in state.take_outbox() {
+            self.send(&message);
+        }
+    }
+
+    fn send(&self, message: &Message) {
+        if let Some(connection) = &self.connection {
+            let _ = connection.socket.send_with_str(&message.to_json());
+        }
+    }
+
+    fn view_lobby(&self, ctx: &Context<Self>) -> Html {
+        let connect_onclick = ctx.link().callback(|_| Msg::Connect);
+        let input_class = "bg-dark2 rounded px-2 py-1 text-base";
+        html! {
+            <div class="flex flex-col items-center gap-4">
+                <p>{"Play against someone else connected to the same relay server and room."}</p>
+                <label class="flex items-center gap-2">
+                    {"Server"}
+                    <input
+                        class={input_class}
+                        ref={self.url_node.clone()}
+                        value={format!("ws://localhost:{DEFAULT_PORT}")}
+                    />
+                </label>
+                <label class="flex items-center gap-2">
+                    {"Room"}
+                    <input class={input_class} ref={self.room_node.clone()} value="typetris" />
+                </label>
+                if self.status == Status::Disconnected {
+                    <p class="text-error">{"Couldn't reach the server, or the connection was lost."}</p>
+                }
+                <button
+                    class="bg-primary bg-base max-w-fit rounded-full px-4 py-2 text-sm font-semibold shadow-sm"
+                    onclick={connect_onclick}
+                >
+                    {"Find a match"}
+                </button>
+            </div>
+        }
+    }
+
+    fn view_match(&self, ctx: &Context<Self>, state: &OnlineMatch) -> Html {
+        let connect_onclick = ctx.link().callback(|_| Msg::Connect);
+        let view_player = |player: Player, index: usize| {
+            let game = state.game(player);
+            let style = format!(
+                "aspect-ratio: {} / {}",
+                game.board().width(),
+                game.board().height()
+            );
+            let name = if player == state.player() {
+                "You"
+            } else {
+                "Opponent"
+            };
+            html! {
+                <div class="flex h-full min-h-0 flex-col items-center gap-2">
+                    <p>
+                        {name}{" — "}{game.score()}
+                        if game.incoming_garbage() > 0 {
+                            <span class="text-warning">{format!(" (+{})", game.incoming_garbage())}</span>
+                        }
+                    </p>
+                    <canvas
+                        class="min-h-0 w-full max-w-screen-sm lg:h-full lg:w-auto lg:max-w-none"
+                        style={style}
+                        ref={self.canvas_nodes[index].clone()}
+                    />
+                </div>
+            }
+        };
+        html! {
+            <>
+                if let Some(winner) = state.winner() {
+                    <h1 class="text-light1 text-4xl font-bold">
+                        {if winner == state.player() { "You win!" } else { "You lose!" }}
+                    </h1>
+                }
+                if state.desynced() {
+                    <p class="text-error">{"Your copy of the opponent's game has drifted out of sync."}</p>
+                }
+                <div class="flex min-h-0 w-full flex-1 flex-row items-stretch justify-center gap-8">
+                    {view_player(Player::One, 0)}
+                    {view_player(Player::Two, 1)}
+                </div>
+                <TouchControls
+                    layout={ctx.props().layout}
+                    onevent={ctx.link().callback(Msg::Control)}
+                    input={self.input_node.clone()}
+                />
+                if state.is_over() {
+                    <button
+                        class="bg-primary bg-base max-w-fit rounded-full px-4 py-2 text-sm font-semibold shadow-sm"
+                        onclick={connect_onclick}
+                    >
+                        {"Find another match"}
+                    </button>
+                }
+            </>
+        }
+    }
+}
+
+impl Component for Online {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let _frame_handle = request_frame(ctx.link().callback(Msg::Tick));
+        let callback = ctx.link().callback(Msg::Key);
+        let _key_listeners = key_listeners(callback);
+        Self {
+            _frame_handle,
+            _key_listeners,
+            connection: None,
+            status: Status::Idle,
+            state: None,
+            url_node: NodeRef::default(),
+            room_node: NodeRef::default(),
+            canvas_nodes: Default::default(),
+            resize_watcher: ResizeWatcher::new(ctx.link().callback(|_| Msg::Resized)),
+            input_node: NodeRef::default(),
+            animations: Default::default(),
+            canvases_sized: false,
+            last_timestamp: None,
+            swatch: Swatch::from(ctx.props().theme),
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, _old_props: &Self::Properties) -> bool {
+        self.swatch = Swatch::from(ctx.props().theme);
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        html! {
+            <div class="flex h-screen w-full flex-col items-center justify-center gap-4 overflow-hidden p-4 pt-12">
+                {match (&self.status, &self.state) {
+                    (Status::Connecting, _) => html! { <p>{"Connecting…"}</p> },
+                    (Status::Waiting, _) => html! { <p>{"Waiting for an opponent…"}</p> },
+                    (Status::Playing, Some(state)) => self.view_match(ctx, state),
+                    _ => self.view_lobby(ctx),
+                }}
+            </div>
+        }
+    }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
+        let (Status::Playing, Some(state)) = (self.status, &self.state) else {
+            return;
+        };
+        if !self.canvases_sized {
+            // The canvases are new for every match.
+            self.canvas_nodes
+                .iter()
+                .for_each(|node| self.resize_watcher.observe(node));
+        }
+        let fit = self.resize_watcher.take_stale() || !self.canvases_sized;
+        for ((node, animations), player) in self
+            .canvas_nodes
+            .iter()
+            .zip(&mut self.animations)
+            .zip([Player::One, Player::Two])
+        {
+            let game = state.game(player);
+            animations.follow(game);
+            render_board(node, game, animations, &self.swatch, fit);
+        }
+        self.canvases_sized = true;
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Tick(timestamp) => self.tick(ctx, timestamp),
+            Msg::Key(e) => self.key(ctx, e),
+            Msg::Resized => true,
+            Msg::Control(event) => self.play(event),
+            Msg::Connect => self.connect(ctx),
+            Msg::Opened => self.opened(),
+            Msg::Received(text) => self.received(text),
+            Msg::Closed => self.closed(),
+        }
+    }
+}
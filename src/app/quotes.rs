@@ -0,0 +1,65 @@
+//! Typing passages from books and speeches: a game of the chosen quote's words, with a list to
+//! choose another from.
+
+use gloo::storage::{LocalStorage, Storage};
+use typetris::game::layout::Layout;
+use typetris::game::quotes::QUOTES;
+use web_sys::HtmlSelectElement;
+use yew::prelude::*;
+
+use super::game::Game;
+use super::rules::Rules;
+use super::theme::Theme;
+
+const STORAGE_KEY: &str = "typetris.quote";
+
+#[derive(Debug, Clone, Properties, PartialEq, Eq)]
+pub(crate) struct Props {
+    pub(crate) theme: &'static Theme,
+    pub(crate) layout: Layout,
+    pub(crate) rules: Rules,
+}
+
+/// A game of the quote picked last time, or the first.
+#[function_component(Quotes)]
+pub(crate) fn quotes(props: &Props) -> Html {
+    let index = use_state(|| {
+        LocalStorage::get::<usize>(STORAGE_KEY)
+            .ok()
+            .filter(|&i| i < QUOTES.len())
+            .unwrap_or_default()
+    });
+    let onchange = {
+        let index = index.clone();
+        move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            let new = select.selected_index() as usize;
+            if new < QUOTES.len() {
+                // Not being able to save isn't worth bothering the player about.
+                let _ = LocalStorage::set(STORAGE_KEY, new);
+                index.set(new);
+            }
+        }
+    };
+    let quote = QUOTES[*index];
+    html! {
+        <>
+            <Game
+                text={Some(AttrValue::Static(quote.text))}
+                title={Some(AttrValue::Static(quote.title))}
+                theme={props.theme}
+                layout={props.layout}
+                rules={props.rules}
+            />
+            <select
+                class="bg-dark2 fixed bottom-4 left-4 rounded-full px-3 py-1 text-sm"
+                aria-label="Quote"
+                {onchange}
+            >
+                {for QUOTES.iter().enumerate().map(|(i, q)| html! {
+                    <option selected={i == *index}>{format!("{} · {}", q.title, q.author)}</option>
+                })}
+            </select>
+        </>
+    }
+}
@@ -0,0 +1,46 @@
+//! Optional rules the player can turn on for the games they start themselves. The daily challenge
+//! and lessons always play by their own rules, so everyone's results stay comparable.
+
+use gloo::storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+use typetris::game::settings::Settings;
+
+const STORAGE_KEY: &str = "typetris.rules";
+
+/// How often a block is special when [`Rules::special_blocks`] is on.
+const SPECIAL_BLOCK_CHANCE: f64 = 0.1;
+
+/// Which optional rules are on, as saved in local storage. They're all off to begin with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub(crate) struct Rules {
+    /// Longer words may come wrapped into the shape of a Tetris piece.
+    pub(crate) shaped_pieces: bool,
+    /// Some blocks are bombs, wildcards, locked or ice.
+    pub(crate) special_blocks: bool,
+}
+
+impl Rules {
+    /// The rules chosen on an earlier visit, or none.
+    #[inline]
+    pub(crate) fn load() -> Self {
+        LocalStorage::get(STORAGE_KEY).unwrap_or_default()
+    }
+
+    /// Remember these rules for next time.
+    #[inline]
+    pub(crate) fn save(self) {
+        // Not being able to save isn't worth bothering the player about.
+        let _ = LocalStorage::set(STORAGE_KEY, self);
+    }
+
+    /// `settings` with these rules on.
+    pub(crate) fn apply(self, settings: Settings) -> Settings {
+        settings
+            .with_shaped_pieces(self.shaped_pieces)
+            .with_special_block_chance(if self.special_blocks {
+                SPECIAL_BLOCK_CHANCE
+            } else {
+                0.0
+            })
+    }
+}
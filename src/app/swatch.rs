@@ -0,0 +1,35 @@
+use super::theme::Theme;
+
+/// The colours boards are drawn in.
+#[derive(Debug, Clone)]
+pub(super) struct Swatch {
+    pub(super) reticle_color: String,
+    pub(super) bg_color: String,
+    pub(super) regular_block_color: String,
+    pub(super) disabled_block_color: String,
+    pub(super) success_color: String,
+    pub(super) error_color: String,
+    pub(super) bomb_color: String,
+    pub(super) ice_color: String,
+    pub(super) locked_color: String,
+    pub(super) text_color: String,
+    pub(super) separator_color: String,
+}
+
+impl From<&Theme> for Swatch {
+    fn from(theme: &Theme) -> Self {
+        Self {
+            reticle_color: theme.reticle.into(),
+            bg_color: theme.dark2.into(),
+            regular_block_color: theme.primary.into(),
+            disabled_block_color: theme.dark3.into(),
+            success_color: theme.success.into(),
+            error_color: theme.error.into(),
+            bomb_color: theme.warning.into(),
+            ice_color: theme.ice.into(),
+            locked_color: theme.locked.into(),
+            text_color: theme.block_text.into(),
+            separator_color: theme.separator.into(),
+        }
+    }
+}
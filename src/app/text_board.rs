@@ -0,0 +1,61 @@
+//! The board as an HTML table, for players who can't use the canvas.
+
+use typetris::game::board::Board;
+use yew::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mark {
+    Plain,
+    /// Part of the focused block, not typed yet.
+    Focused,
+    Correct,
+    Wrong,
+}
+
+/// Render `board` as a table of letters, one row of the table per row of the board. Letters of
+/// the focused block stand out, and wrong ones are underlined as well as coloured.
+pub(super) fn view_text_board(board: &Board) -> Html {
+    let mut grid = vec![vec![None; board.width() as usize]; board.height() as usize];
+    let focused = board.get_focused_index();
+    for (index, block) in board.blocks().iter().enumerate() {
+        let typed = block.input_text().as_bytes();
+        for (i, (position, letter)) in block.cells().zip(block.assigned_text().chars()).enumerate()
+        {
+            let mark = match typed.get(i) {
+                _ if focused != Some(index) => Mark::Plain,
+                None => Mark::Focused,
+                Some(&ch) if block.matches(i, ch as char) => Mark::Correct,
+                Some(_) => Mark::Wrong,
+            };
+            let letter = if block.is_garbage() { '#' } else { letter };
+            grid[position.y as usize][position.x as usize] = Some((letter, mark));
+        }
+    }
+    html! {
+        <table class="font-mono text-base leading-tight" aria-label="Board, top row first">
+            {for grid.iter().map(|row| html! {
+                <tr>
+                    {for row.iter().map(|cell| match cell {
+                        None => html! { <td class="text-dark3 w-4 text-center">{"·"}</td> },
+                        Some((letter, mark)) => {
+                            let class = match mark {
+                                Mark::Plain => classes!(),
+                                Mark::Focused => classes!("text-primary", "font-bold"),
+                                Mark::Correct => classes!("text-success", "font-bold"),
+                                Mark::Wrong => {
+                                    classes!("text-error", "font-bold", "underline", "decoration-2")
+                                }
+                            };
+                            let label = (*mark == Mark::Wrong).then(|| format!("{letter}, wrong"));
+                            html! {
+                                <td class={classes!("w-4", "text-center", class)} aria-label={label}>
+                                    {letter}
+                                </td>
+                            }
+                        }
+                    })}
+                </tr>
+            })}
+        </table>
+    }
+}
@@ -0,0 +1,213 @@
+//! Colour themes. A theme sets the `--color-*` custom properties the page is styled with, and the
+//! colours the boards are drawn in, so switching one recolours everything at once.
+
+use gloo::{
+    storage::{LocalStorage, Storage},
+    utils::document,
+};
+use web_sys::{HtmlElement, wasm_bindgen::JsCast};
+
+const STORAGE_KEY: &str = "typetris.theme";
+/// Where the high contrast toggle that themes replaced was saved.
+const HIGH_CONTRAST_KEY: &str = "typetris.high_contrast";
+
+/// A named set of colours. The dark colours are backgrounds and the light ones foregrounds, even
+/// in light themes where they're the other way around.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Theme {
+    /// Saved to remember the theme between visits, so it shouldn't change.
+    pub(crate) id: &'static str,
+    pub(crate) name: &'static str,
+    pub(crate) primary: &'static str,
+    pub(crate) accent: &'static str,
+    pub(crate) dark1: &'static str,
+    pub(crate) dark2: &'static str,
+    pub(crate) dark3: &'static str,
+    pub(crate) light1: &'static str,
+    pub(crate) light2: &'static str,
+    pub(crate) light3: &'static str,
+    pub(crate) success: &'static str,
+    pub(crate) warning: &'static str,
+    pub(crate) error: &'static str,
+    pub(crate) reticle: &'static str,
+    pub(crate) ice: &'static str,
+    pub(crate) locked: &'static str,
+    /// Letters on blocks.
+    pub(crate) block_text: &'static str,
+    /// Lines between the cells of a block.
+    pub(crate) separator: &'static str,
+}
+
+pub(crate) const DARK: Theme = Theme {
+    id: "dark",
+    name: "Dark",
+    primary: "#7c3aed",
+    accent: "#6517ea",
+    dark1: "#221736",
+    dark2: "#3b2d53",
+    dark3: "#55476b",
+    light1: "#ffffff",
+    light2: "#f7f4fa",
+    light3: "#e3deed",
+    success: "#0dba5c",
+    warning: "#ff7300",
+    error: "#f53733",
+    reticle: "#ff69b4",
+    ice: "#38bdf8",
+    locked: "#a16207",
+    block_text: "#ffffff",
+    separator: "#000000",
+};
+
+pub(crate) const LIGHT: Theme = Theme {
+    id: "light",
+    name: "Light",
+    dark1: "#f7f4fa",
+    dark2: "#e3deed",
+    dark3: "#8a7d9e",
+    light1: "#1a1226",
+    light2: "#2e2540",
+    light3: "#4a3f5c",
+    reticle: "#db2777",
+    separator: "#f7f4fa",
+    ..DARK
+};
+
+pub(crate) const SOLARIZED: Theme = Theme {
+    id: "solarized",
+    name: "Solarized",
+    primary: "#268bd2",
+    accent: "#2aa198",
+    dark1: "#002b36",
+    dark2: "#073642",
+    dark3: "#586e75",
+    light1: "#fdf6e3",
+    light2: "#eee8d5",
+    light3: "#93a1a1",
+    success: "#859900",
+    warning: "#cb4b16",
+    error: "#dc322f",
+    reticle: "#d33682",
+    ice: "#2aa198",
+    locked: "#b58900",
+    block_text: "#fdf6e3",
+    separator: "#002b36",
+};
+
+/// The palette from `ref.json`.
+pub(crate) const ORCHID: Theme = Theme {
+    id: "orchid",
+    name: "Orchid",
+    primary: "#9929bd",
+    accent: "#80229e",
+    dark1: "#2e1736",
+    dark2: "#4a2d53",
+    dark3: "#62476b",
+    light1: "#ffffff",
+    light2: "#f9f4fa",
+    light3: "#e9deed",
+    ..DARK
+};
+
+/// Stronger contrast, and colours that stay apart for colour-blind players (Okabe-Ito).
+pub(crate) const COLOUR_BLIND_DARK: Theme = Theme {
+    id: "colour-blind-dark",
+    name: "Colour-blind dark",
+    primary: "#cc79a7",
+    accent: "#a8618a",
+    dark1: "#000000",
+    dark2: "#1a1a1a",
+    dark3: "#666666",
+    light1: "#ffffff",
+    light2: "#ffffff",
+    light3: "#ffffff",
+    success: "#0072b2",
+    warning: "#e69f00",
+    error: "#d55e00",
+    reticle: "#f0e442",
+    ice: "#56b4e9",
+    locked: "#a16207",
+    block_text: "#ffffff",
+    separator: "#000000",
+};
+
+pub(crate) const COLOUR_BLIND_LIGHT: Theme = Theme {
+    id: "colour-blind-light",
+    name: "Colour-blind light",
+    dark1: "#ffffff",
+    dark2: "#f0f0f0",
+    dark3: "#767676",
+    light1: "#000000",
+    light2: "#000000",
+    light3: "#1a1a1a",
+    reticle: "#000000",
+    locked: "#f0e442",
+    block_text: "#000000",
+    separator: "#ffffff",
+    ..COLOUR_BLIND_DARK
+};
+
+pub(crate) const THEMES: [Theme; 6] = [
+    DARK,
+    LIGHT,
+    SOLARIZED,
+    ORCHID,
+    COLOUR_BLIND_DARK,
+    COLOUR_BLIND_LIGHT,
+];
+
+impl Theme {
+    /// Look up a theme by its id.
+    pub(crate) fn get(id: &str) -> Option<&'static Theme> {
+        THEMES.iter().find(|theme| theme.id == id)
+    }
+
+    /// The theme chosen on an earlier visit, or the default one. Players who turned high contrast
+    /// on before there were themes get the dark colour-blind theme, which replaced it.
+    pub(crate) fn load() -> &'static Theme {
+        LocalStorage::get::<String>(STORAGE_KEY)
+            .ok()
+            .and_then(|id| Self::get(&id))
+            .or_else(|| {
+                LocalStorage::get::<bool>(HIGH_CONTRAST_KEY)
+                    .unwrap_or(false)
+                    .then(|| Self::get(COLOUR_BLIND_DARK.id))
+                    .flatten()
+            })
+            .unwrap_or(&THEMES[0])
+    }
+
+    /// The custom properties this theme sets, and their values.
+    fn properties(&self) -> [(&'static str, &'static str); 16] {
+        [
+            ("--color-primary", self.primary),
+            ("--color-accent", self.accent),
+            ("--color-dark1", self.dark1),
+            ("--color-dark2", self.dark2),
+            ("--color-dark3", self.dark3),
+            ("--color-light1", self.light1),
+            ("--color-light2", self.light2),
+            ("--color-light3", self.light3),
+            ("--color-success", self.success),
+            ("--color-warning", self.warning),
+            ("--color-error", self.error),
+            ("--color-reticle", self.reticle),
+            ("--color-ice", self.ice),
+            ("--color-locked", self.locked),
+            ("--color-block-text", self.block_text),
+            ("--color-separator", self.separator),
+        ]
+    }
+
+    /// Restyle the page in this theme, and remember it for next time.
+    pub(crate) fn apply(&self) {
+        let root: HtmlElement = document().document_element().unwrap().unchecked_into();
+        let style = root.style();
+        for (property, value) in self.properties() {
+            // Setting a custom property only fails if the name is invalid.
+            style.set_property(property, value).unwrap();
+        }
+        // Not being able to save isn't worth bothering the player about.
+        let _ = LocalStorage::set(STORAGE_KEY, self.id);
+    }
+}
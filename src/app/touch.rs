@@ -0,0 +1,189 @@
+//! Playing on phones and tablets: swipes on the board, on-screen buttons, and a hidden text field
+//! that brings up the soft keyboard.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gloo::events::EventListener;
+use typetris::game::Event;
+use typetris::game::layout::Layout;
+use web_sys::{
+    CompositionEvent, HtmlInputElement,
+    wasm_bindgen::{JsCast, UnwrapThrowExt},
+};
+use yew::prelude::*;
+
+use super::game::key_to_event;
+
+/// How far a touch has to move, in CSS pixels, to be a swipe rather than a tap.
+const SWIPE_DISTANCE: f64 = 30.0;
+
+/// What the hidden text field is reset to after every edit. Soft keyboards don't send anything for
+/// backspace in an empty field, so it always has something to delete.
+const FILLER: &str = " ";
+
+/// Follows one finger across the board.
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct Swipe {
+    start: Option<(f64, f64)>,
+}
+
+/// Where the touch that changed in `event` is, in CSS pixels.
+fn touch_position(event: &TouchEvent) -> Option<(f64, f64)> {
+    let touch = event.changed_touches().get(0)?;
+    Some((touch.client_x() as f64, touch.client_y() as f64))
+}
+
+impl Swipe {
+    pub(super) fn start(&mut self, event: &TouchEvent) {
+        self.start = touch_position(event);
+    }
+
+    /// Finish the swipe, and return the events it stands for, or `None` if it was a tap. Sideways
+    /// swipes move the focused block one column for every `cell_width` pixels, down drops it and
+    /// up rotates it.
+    pub(super) fn end(&mut self, event: &TouchEvent, cell_width: f64) -> Option<Vec<Event>> {
+        let (start_x, start_y) = self.start.take()?;
+        let (x, y) = touch_position(event)?;
+        let (dx, dy) = (x - start_x, y - start_y);
+        if dx.abs().max(dy.abs()) < SWIPE_DISTANCE {
+            return None;
+        }
+        Some(if dx.abs() > dy.abs() {
+            let columns = (dx.abs() / cell_width).round().max(1.0) as usize;
+            let event = if dx < 0.0 { Event::Left } else { Event::Right };
+            vec![event; columns]
+        } else if dy > 0.0 {
+            vec![Event::Next]
+        } else {
+            vec![Event::Rotate]
+        })
+    }
+}
+
+/// The events that turn the typed text `old` into `new`: deleting back to where they differ,
+/// then typing the rest of `new`.
+fn diff_text(old: &str, new: &str) -> Vec<Event> {
+    let common = old
+        .chars()
+        .zip(new.chars())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let deletes = old.chars().count() - common;
+    let types = new.chars().skip(common).map(Event::Type);
+    vec![Event::Delete; deletes]
+        .into_iter()
+        .chain(types)
+        .collect()
+}
+
+#[derive(Debug, Clone, Properties, PartialEq)]
+pub(super) struct TouchControlsProps {
+    /// Layout of the physical keyboard, if there is one, to know which keys the game handles.
+    pub(super) layout: Layout,
+    pub(super) onevent: Callback<Event>,
+    /// The hidden text field, so that tapping the board can focus it.
+    pub(super) input: NodeRef,
+}
+
+/// Buttons for everything that isn't typing, and the hidden text field typing goes through. Only
+/// shown on touch screens.
+#[function_component(TouchControls)]
+pub(super) fn touch_controls(props: &TouchControlsProps) -> Html {
+    // Text of the word the soft keyboard is composing, as far as it's been passed on already.
+    let composed: Rc<RefCell<String>> = use_mut_ref(String::new);
+
+    let emit_all = {
+        let onevent = props.onevent.clone();
+        move |events: Vec<Event>| events.into_iter().for_each(|e| onevent.emit(e))
+    };
+    let layout = props.layout;
+    let onkeydown = move |e: KeyboardEvent| {
+        // Keys the game knows are handled by the window's listener, and shouldn't also reach the
+        // field as input.
+        if key_to_event(&e, layout).is_some() {
+            e.prevent_default();
+        }
+    };
+    let oninput = {
+        let composed = composed.clone();
+        let emit_all = emit_all.clone();
+        move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let data = e.data().unwrap_or_default();
+            if e.is_composing() {
+                let mut composed = composed.borrow_mut();
+                emit_all(diff_text(&composed, &data));
+                *composed = data;
+                // Changing the field in the middle of a composition confuses the keyboard.
+                return;
+            }
+            match e.input_type().as_str() {
+                "insertText" => emit_all(data.chars().map(Event::Type).collect()),
+                "deleteContentBackward" => emit_all(vec![Event::Delete]),
+                _ => (),
+            }
+            input.set_value(FILLER);
+        }
+    };
+    // Yew has no listeners for composition events, so they're added by hand.
+    use_effect_with(props.input.clone(), move |node| {
+        let input: HtmlInputElement = node.cast().unwrap();
+        let started = {
+            let composed = composed.clone();
+            EventListener::new(&input, "compositionstart", move |_| {
+                composed.borrow_mut().clear()
+            })
+        };
+        let ended = EventListener::new(&input.clone(), "compositionend", move |e| {
+            let e: &CompositionEvent = e.dyn_ref().unwrap_throw();
+            emit_all(diff_text(&composed.borrow(), &e.data().unwrap_or_default()));
+            composed.borrow_mut().clear();
+            input.set_value(FILLER);
+        });
+        move || drop((started, ended))
+    });
+
+    let button = |label: &'static str, event: Event| {
+        let onevent = props.onevent.clone();
+        html! {
+            <button
+                class="bg-dark2 min-w-16 rounded-full px-4 py-3 text-sm"
+                onclick={move |_| onevent.emit(event)}
+            >
+                {label}
+            </button>
+        }
+    };
+    let focus_input = {
+        let input = props.input.clone();
+        move |_| {
+            if let Some(input) = input.cast::<HtmlInputElement>() {
+                let _ = input.focus();
+            }
+        }
+    };
+    html! {
+        <div class="mt-4 hidden flex-wrap justify-center gap-2 pointer-coarse:flex">
+            <input
+                class="sr-only"
+                aria-label="Type here"
+                autocomplete="off"
+                autocapitalize="off"
+                spellcheck="false"
+                value={FILLER}
+                ref={props.input.clone()}
+                {onkeydown}
+                {oninput}
+            />
+            <button class="bg-primary rounded-full px-4 py-3 text-sm" onclick={focus_input}>
+                {"Keyboard"}
+            </button>
+            {button("Left", Event::Left)}
+            {button("Right", Event::Right)}
+            {button("Rotate", Event::Rotate)}
+            {button("Drop", Event::Next)}
+            {button("Delete", Event::Delete)}
+        </div>
+    }
+}
@@ -0,0 +1,241 @@
+use gloo::{events::EventListener, render::AnimationFrame};
+use typetris::game::Event;
+use typetris::game::bot::Bot;
+use typetris::game::layout::Layout;
+use typetris::game::settings::Settings;
+use typetris::game::versus::{Player, VersusMatch};
+use yew::prelude::*;
+
+use super::animation::Animations;
+use super::audio;
+use super::canvas::{ResizeWatcher, render_board};
+use super::game::{frame_delta, key_listeners, key_to_event, request_frame};
+use super::swatch::Swatch;
+use super::theme::Theme;
+use super::touch::TouchControls;
+
+/// Who plays against the person at the keyboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Opponent {
+    Bot,
+    /// Both players share the keyboard, and control passes to the other player every time a
+    /// block is dropped.
+    HotSeat,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Msg {
+    /// An animation frame, with its timestamp.
+    Tick(f64),
+    Key(KeyboardEvent),
+    /// A canvas was resized on screen.
+    Resized,
+    /// An event from the on-screen controls.
+    Control(Event),
+    NewGame,
+}
+
+#[derive(Debug, Clone, Properties, PartialEq, Eq)]
+pub(crate) struct Props {
+    pub(crate) opponent: Opponent,
+    /// Colours to draw the boards in.
+    pub(crate) theme: &'static Theme,
+    /// Keyboard layout, for its shortcuts.
+    pub(crate) layout: Layout,
+}
+
+pub(crate) struct Versus {
+    _frame_handle: AnimationFrame,
+    _key_listeners: [EventListener; 2],
+    state: VersusMatch,
+    animations: [Animations; 2],
+    bot: Bot,
+    /// The player the keyboard currently controls.
+    active: Player,
+    canvas_nodes: [NodeRef; 2],
+    resize_watcher: ResizeWatcher,
+    /// The hidden text field that brings up the soft keyboard.
+    input_node: NodeRef,
+    last_timestamp: Option<f64>,
+    swatch: Swatch,
+}
+
+impl Versus {
+    fn tick(&mut self, ctx: &Context<Self>, timestamp: f64) -> bool {
+        self._frame_handle = request_frame(ctx.link().callback(Msg::Tick));
+        let delta_time = frame_delta(&mut self.last_timestamp, timestamp);
+
+        let mut ret = false;
+        if ctx.props().opponent == Opponent::Bot {
+            for event in self.bot.think(self.state.game(Player::Two), delta_time) {
+                ret |= self.state.handle_event(Player::Two, event);
+            }
+        }
+        ret |= self.state.tick(delta_time);
+        for (animations, player) in self.animations.iter_mut().zip([Player::One, Player::Two]) {
+            animations.tick(delta_time);
+            let notices = self.state.take_notices(player);
+            // The bot's typing would drown out the player's own.
+            if player == Player::One || ctx.props().opponent == Opponent::HotSeat {
+                audio::play(&notices);
+            }
+            animations.extend(notices);
+        }
+        // Falling and sliding blocks are drawn between cells, so they move on every frame.
+        ret || !self.state.is_over() || self.animations.iter().any(Animations::is_playing)
+    }
+
+    fn key(&mut self, ctx: &Context<Self>, event: KeyboardEvent) -> bool {
+        let Some(event) = key_to_event(&event, ctx.props().layout) else {
+            return false;
+        };
+        self.play(ctx, event)
+    }
+
+    /// Pass `event` on to the player the keyboard controls.
+    fn play(&mut self, ctx: &Context<Self>, event: Event) -> bool {
+        let ret = self.state.handle_event(self.active, event);
+        if ret && matches!(event, Event::Next) && ctx.props().opponent == Opponent::HotSeat {
+            // Whatever the last player was holding shouldn't keep moving their blocks.
+            self.state.handle_event(self.active, Event::LeftReleased);
+            self.state.handle_event(self.active, Event::RightReleased);
+            self.active = self.active.opponent();
+        }
+        ret
+    }
+
+    fn new_game(&mut self) -> bool {
+        self.last_timestamp = None;
+        self.animations = Default::default();
+        self.active = Player::One;
+        self.bot = Bot::default();
+        self.state.handle_event(Player::One, Event::NewGame)
+    }
+
+    fn view_player(&self, ctx: &Context<Self>, player: Player) -> Html {
+        let game = self.state.game(player);
+        let style = format!(
+            "aspect-ratio: {} / {}",
+            game.board().width(),
+            game.board().height()
+        );
+        let name = match (player, ctx.props().opponent) {
+            (Player::One, _) => "Player 1",
+            (Player::Two, Opponent::Bot) => "Bot",
+            (Player::Two, Opponent::HotSeat) => "Player 2",
+        };
+        let index = match player {
+            Player::One => 0,
+            Player::Two => 1,
+        };
+        let active = self.active == player && !self.state.is_over();
+        html! {
+            <div class="flex h-full min-h-0 flex-col items-center gap-2">
+                <p class={classes!(active.then_some("text-primary"))}>
+                    {name}{" — "}{game.score()}
+                    if game.incoming_garbage() > 0 {
+                        <span class="text-warning">{format!(" (+{})", game.incoming_garbage())}</span>
+                    }
+                </p>
+                <canvas
+                    class="min-h-0 w-full max-w-screen-sm lg:h-full lg:w-auto lg:max-w-none"
+                    style={style}
+                    ref={self.canvas_nodes[index].clone()}
+                />
+            </div>
+        }
+    }
+}
+
+impl Component for Versus {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let _frame_handle = request_frame(ctx.link().callback(Msg::Tick));
+        let callback = ctx.link().callback(Msg::Key);
+        let _key_listeners = key_listeners(callback);
+        Self {
+            _frame_handle,
+            _key_listeners,
+            state: VersusMatch::new(Settings::default()),
+            animations: Default::default(),
+            bot: Bot::default(),
+            active: Player::One,
+            canvas_nodes: Default::default(),
+            resize_watcher: ResizeWatcher::new(ctx.link().callback(|_| Msg::Resized)),
+            input_node: NodeRef::default(),
+            last_timestamp: None,
+            swatch: Swatch::from(ctx.props().theme),
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        if ctx.props().opponent != old_props.opponent {
+            self.new_game();
+        }
+        self.swatch = Swatch::from(ctx.props().theme);
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let new_game_onclick = ctx.link().callback(|_| Msg::NewGame);
+        html! {
+            <div class="flex h-screen w-full flex-col items-center justify-center gap-4 overflow-hidden p-4 pt-12">
+                if let Some(winner) = self.state.winner() {
+                    <h1 class="text-light1 text-4xl font-bold">
+                        {match (winner, ctx.props().opponent) {
+                            (Player::One, _) => "Player 1 wins!",
+                            (Player::Two, Opponent::Bot) => "The bot wins!",
+                            (Player::Two, Opponent::HotSeat) => "Player 2 wins!",
+                        }}
+                    </h1>
+                }
+                <div class="flex min-h-0 w-full flex-1 flex-row items-stretch justify-center gap-8">
+                    {self.view_player(ctx, Player::One)}
+                    {self.view_player(ctx, Player::Two)}
+                </div>
+                <TouchControls
+                    layout={ctx.props().layout}
+                    onevent={ctx.link().callback(Msg::Control)}
+                    input={self.input_node.clone()}
+                />
+                <button
+                    class="bg-primary bg-base max-w-fit rounded-full px-4 py-2 text-sm font-semibold shadow-sm"
+                    onclick={new_game_onclick}
+                >
+                    {"Restart"}
+                </button>
+            </div>
+        }
+    }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, first_render: bool) {
+        if first_render {
+            self.canvas_nodes
+                .iter()
+                .for_each(|node| self.resize_watcher.observe(node));
+        }
+        let fit = self.resize_watcher.take_stale() || first_render;
+        for ((node, animations), player) in self
+            .canvas_nodes
+            .iter()
+            .zip(&mut self.animations)
+            .zip([Player::One, Player::Two])
+        {
+            let game = self.state.game(player);
+            animations.follow(game);
+            render_board(node, game, animations, &self.swatch, fit);
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Tick(timestamp) => self.tick(ctx, timestamp),
+            Msg::Key(e) => self.key(ctx, e),
+            Msg::Resized => true,
+            Msg::Control(event) => self.play(ctx, event),
+            Msg::NewGame => self.new_game(),
+        }
+    }
+}
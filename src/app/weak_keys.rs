@@ -0,0 +1,86 @@
+//! The player's key profile for practice mode: remembered between visits, and shown as a chart of
+//! the keys they miss most.
+
+use std::cell::Cell;
+
+use gloo::storage::{LocalStorage, Storage};
+use typetris::game::layout::Layout;
+use typetris::game::practice::{KeyProfile, Tally};
+use yew::prelude::*;
+
+const STORAGE_KEY: &str = "typetris.practice";
+/// How many letters the chart shows.
+const CHART_LETTERS: usize = 8;
+/// How many bigrams are listed under the chart.
+const LISTED_BIGRAMS: usize = 5;
+
+thread_local! {
+    /// Whether the saved profile has been faded for this visit yet.
+    static DECAYED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// The profile saved on an earlier visit, or an empty one. The first time it's loaded on a visit,
+/// it fades a little and is saved that way, so that it moves on once the player has improved.
+pub(super) fn load() -> KeyProfile {
+    let mut profile: KeyProfile = LocalStorage::get(STORAGE_KEY).unwrap_or_default();
+    if !DECAYED.replace(true) {
+        profile.decay();
+        save(&profile);
+    }
+    profile
+}
+
+pub(super) fn save(profile: &KeyProfile) {
+    // Not being able to save isn't worth bothering the player about.
+    let _ = LocalStorage::set(STORAGE_KEY, profile);
+}
+
+/// Miss rates of `tallies`, in a line of text.
+fn view_breakdown<'a>(title: &str, tallies: impl Iterator<Item = (&'a str, &'a Tally)>) -> Html {
+    let text = tallies
+        .map(|(name, tally)| format!("{name} {:.0}%", tally.miss_rate() * 100.0))
+        .collect::<Vec<_>>()
+        .join(", ");
+    html! {
+        <p class="text-light3">{title}{": "}{text}</p>
+    }
+}
+
+/// A bar for each of the letters missed most, longest for the worst, the worst bigrams, and how
+/// each row and finger of `layout` is doing.
+pub(super) fn view_weak_keys(profile: &KeyProfile, layout: Layout) -> Html {
+    let letters = profile.weak_letters(CHART_LETTERS);
+    let bigrams = profile.weak_bigrams(LISTED_BIGRAMS);
+    let worst = letters.first().map_or(1.0, |&(_, rate)| rate);
+    html! {
+        <div class="mt-4 flex w-64 flex-col gap-1 text-sm">
+            <p class="text-light2">{"Weak keys"}</p>
+            if letters.is_empty() {
+                <p class="text-light3">{"Nothing missed yet."}</p>
+            }
+            {for letters.iter().map(|&(letter, rate)| html! {
+                <div class="flex items-center gap-2" aria-label={format!("{letter}, {:.0}% missed", rate * 100.0)}>
+                    <span class="text-primary w-4 font-mono">{letter}</span>
+                    <div
+                        class="bg-error h-3 rounded"
+                        style={format!("width: {:.0}%", rate / worst * 100.0)}
+                    />
+                    <span class="text-light3">{format!("{:.0}%", rate * 100.0)}</span>
+                </div>
+            })}
+            if !bigrams.is_empty() {
+                <p class="text-light3">
+                    {"Pairs: "}
+                    {bigrams.iter().map(|(bigram, _)| *bigram).collect::<Vec<_>>().join(", ")}
+                </p>
+            }
+            if !letters.is_empty() {
+                {view_breakdown("Rows", profile.by_row(layout).iter().map(|(row, t)| (row.name(), t)))}
+                {view_breakdown(
+                    "Fingers",
+                    profile.by_finger(layout).iter().map(|(finger, t)| (finger.name(), t)),
+                )}
+            }
+        </div>
+    }
+}
@@ -0,0 +1,93 @@
+//! Delayed auto shift (DAS) and auto-repeat rate (ARR) for held movement keys. A held key moves
+//! the block once when pressed, again after a delay, and then repeatedly at a fixed rate, the same
+//! on every machine regardless of its key repeat settings.
+
+/// A horizontal direction to shift blocks in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Direction {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct AutoShift {
+    /// Time from pressing a key to its first repeat.
+    delay: f64,
+    /// Time between repeats after that. Zero shifts all the way across at once.
+    interval: f64,
+    held: Option<Direction>,
+    /// Time left until the next repeat.
+    timer: f64,
+}
+
+impl AutoShift {
+    #[inline]
+    pub(super) fn new(delay: f64, interval: f64) -> Self {
+        Self {
+            delay,
+            interval,
+            held: None,
+            timer: 0.0,
+        }
+    }
+
+    /// Start holding `direction`, taking over from whichever direction was held before.
+    #[inline]
+    pub(super) fn press(&mut self, direction: Direction) {
+        self.held = Some(direction);
+        self.timer = self.delay;
+    }
+
+    /// Stop holding `direction`. Releasing a key that isn't the one in charge does nothing.
+    #[inline]
+    pub(super) fn release(&mut self, direction: Direction) {
+        if self.held == Some(direction) {
+            self.held = None;
+        }
+    }
+
+    /// Advance the clock by `delta_time`, and return the held direction and how many times it
+    /// repeated, up to `max`.
+    pub(super) fn tick(&mut self, delta_time: f64, max: usize) -> Option<(Direction, usize)> {
+        let direction = self.held?;
+        self.timer -= delta_time;
+        let mut repeats = 0;
+        while self.timer <= 0.0 && repeats < max {
+            repeats += 1;
+            self.timer += self.interval;
+        }
+        if repeats == max {
+            // Don't bank repeats that couldn't be used.
+            self.timer = self.timer.max(0.0);
+        }
+        Some((direction, repeats))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn t0() {
+        let mut shift = AutoShift::new(100.0, 30.0);
+        assert_eq!(shift.tick(1_000.0, 10), None);
+
+        shift.press(Direction::Left);
+        assert_eq!(shift.tick(99.0, 10), Some((Direction::Left, 0)));
+        assert_eq!(shift.tick(1.0, 10), Some((Direction::Left, 1)));
+        assert_eq!(shift.tick(65.0, 10), Some((Direction::Left, 2)));
+
+        // The newest key wins, and letting go of the other one doesn't matter.
+        shift.press(Direction::Right);
+        shift.release(Direction::Left);
+        assert_eq!(shift.tick(100.0, 10), Some((Direction::Right, 1)));
+        shift.release(Direction::Right);
+        assert_eq!(shift.tick(100.0, 10), None);
+
+        let mut shift = AutoShift::new(100.0, 0.0);
+        shift.press(Direction::Right);
+        assert_eq!(shift.tick(100.0, 10), Some((Direction::Right, 10)));
+        assert_eq!(shift.tick(1.0, 10), Some((Direction::Right, 10)));
+    }
+}
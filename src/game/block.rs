@@ -1,78 +1,363 @@
+use std::borrow::Cow;
+
 use getset::{CopyGetters, Getters};
-use rand::{Rng, rng, seq::IteratorRandom};
+use rand::{
+    Rng,
+    seq::{IndexedRandom, IteratorRandom},
+};
 
 use crate::game::board::BoardPosition;
+use crate::game::words::{Preference, WordSource};
 
 include! { "english.rs" }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+/// Text of a garbage cell. It's not a word, so it can never be spawned or typed.
+const GARBAGE_TEXT: &str = "#";
+/// Text of a wildcard cell. Any letter can be typed over it.
+const WILDCARD_TEXT: &str = "?";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum State {
     Settled,
     Falling,
     Interactable,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Getters, CopyGetters)]
+/// What a block does besides being a word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Kind {
+    #[default]
+    Normal,
+    /// Blows up its own row and the rows next to it when it lands.
+    Bomb,
+    /// A single cell that any letter can be typed over.
+    Wildcard,
+    /// Becomes [`Kind::Locked`] when it lands.
+    Locking,
+    /// A full row with a locked block in it doesn't clear. Instead, the locked block takes focus
+    /// where it is, and the row clears once it has been typed again.
+    Locked,
+    /// Has to be typed twice. The first time only cracks the ice, and turns it into a normal
+    /// block.
+    Ice,
+}
+
+impl Kind {
+    pub const SPECIAL: [Self; 4] = [Self::Bomb, Self::Wildcard, Self::Locking, Self::Ice];
+}
+
+/// How a block lays its text out on the board. Every row of a shape is one unbroken run of cells,
+/// and the text fills the rows from top to bottom, left to right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Shape {
+    /// All on one row.
+    #[default]
+    Bar,
+    /// Two rows of the same length, one on top of the other.
+    O,
+    /// Two rows of the same length, the top one shifted one cell to the right.
+    S,
+    /// Two rows of the same length, the bottom one shifted one cell to the right.
+    Z,
+    /// The first letter sits on top of the left end of the rest.
+    L,
+    /// The first letter sits on top of the right end of the rest.
+    J,
+    /// The last letter hangs below the middle of the rest.
+    T,
+}
+
+impl Shape {
+    pub const ALL: [Self; 7] = [
+        Self::Bar,
+        Self::O,
+        Self::S,
+        Self::Z,
+        Self::L,
+        Self::J,
+        Self::T,
+    ];
+
+    /// Whether a word of `len` letters can be laid out in this shape.
+    pub fn fits(self, len: u8) -> bool {
+        match self {
+            Self::Bar => len > 0,
+            Self::O | Self::S | Self::Z => len >= 4 && len.is_multiple_of(2),
+            Self::L | Self::J | Self::T => len >= 3,
+        }
+    }
+
+    /// Width of this shape for a word of `len` letters.
+    pub fn width(self, len: u8) -> u8 {
+        self.rows(len)
+            .map(|(offset, len)| offset + len)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Height of this shape for a word of `len` letters.
+    pub fn height(self, len: u8) -> u8 {
+        self.rows(len).count() as u8
+    }
+
+    /// The rows of this shape for a word of `len` letters, from top to bottom, as pairs of offset
+    /// from the left edge and length.
+    fn rows(self, len: u8) -> impl Iterator<Item = (u8, u8)> {
+        let half = len / 2;
+        let rows = match self {
+            Self::Bar => [Some((0, len)), None],
+            Self::O => [Some((0, half)), Some((0, half))],
+            Self::S => [Some((1, half)), Some((0, half))],
+            Self::Z => [Some((0, half)), Some((1, half))],
+            Self::L => [Some((0, 1)), Some((0, len - 1))],
+            Self::J => [Some((len - 2, 1)), Some((0, len - 1))],
+            Self::T => [Some((0, len - 1)), Some(((len - 2) / 2, 1))],
+        };
+        rows.into_iter().flatten()
+    }
+}
+
+/// Which way a block's text reads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Orientation {
+    /// Rows of the shape read left to right, from top to bottom.
+    #[default]
+    Horizontal,
+    /// The shape turned on its side: rows of the shape become columns that read top to bottom,
+    /// from left to right.
+    Vertical,
+}
+
+impl Orientation {
+    #[inline]
+    pub fn rotated(self) -> Self {
+        match self {
+            Self::Horizontal => Self::Vertical,
+            Self::Vertical => Self::Horizontal,
+        }
+    }
+}
+
+/// An unbroken run of cells of a block on one row of the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Segment {
+    /// Position of the leftmost cell.
+    pub position: BoardPosition,
+    pub len: u8,
+}
+
+impl Segment {
+    /// The x just past the rightmost cell.
+    #[inline]
+    pub fn end_x(&self) -> u8 {
+        self.position.x + self.len
+    }
+
+    #[inline]
+    pub(super) fn intersect_x(&self, other: &Self) -> bool {
+        self.position.x < other.end_x() && other.position.x < self.end_x()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Getters, CopyGetters)]
 pub struct Block {
     #[getset(get_copy = "pub")]
     pub(super) state: State,
-    #[getset(get = "pub")]
-    assigned_text: &'static str,
+    /// Borrowed for words from the built-in list, and owned for text made up on the fly.
+    assigned_text: Cow<'static, str>,
     #[getset(get = "pub")]
     input_text: String,
     #[getset(get_copy = "pub")]
     pub(super) position: BoardPosition,
+    #[getset(get_copy = "pub")]
+    shape: Shape,
+    #[getset(get_copy = "pub")]
+    pub(super) orientation: Orientation,
+    #[getset(get_copy = "pub")]
+    pub(super) kind: Kind,
+    /// Whether this is a settled [`Kind::Locked`] block in a full row, waiting to be typed again.
+    /// It stays part of the stack while it's typed.
+    pub(super) unlocking: bool,
 }
 
 impl Block {
-    pub fn random(board_width: u8) -> Self {
-        let mut rng = rng();
-        let text = *WORDS
-            .iter()
-            .filter(|w| w.len() <= (board_width as usize))
-            .choose(&mut rng)
-            .unwrap();
-        let mut ret = Self {
-            state: State::Interactable,
-            assigned_text: text,
-            input_text: String::new(),
-            position: BoardPosition::new(),
+    pub fn random(board_width: u8, rng: &mut impl Rng) -> Self {
+        Self::from_words(board_width, Shape::Bar, &mut WordSource::Random, &[], rng)
+            .expect("some built-in words fit any board")
+    }
+
+    /// Like [`Self::random`], but the word may be wrapped into any [`Shape`].
+    pub fn random_shaped(board_width: u8, rng: &mut impl Rng) -> Self {
+        let shape = *Shape::ALL.choose(rng).unwrap();
+        Self::from_words(board_width, shape, &mut WordSource::Random, &[], rng)
+            .expect("some built-in words fit any board")
+    }
+
+    /// A random block of one of the [`Kind::SPECIAL`] kinds.
+    pub fn random_special(board_width: u8, rng: &mut impl Rng) -> Self {
+        Self::special_from_words(board_width, &mut WordSource::Random, &[], rng)
+            .expect("some built-in words fit any board")
+    }
+
+    /// A block of one of the [`Kind::SPECIAL`] kinds, with a word from `words` unless it's a
+    /// wildcard, or `None` if it needed a word and there were none.
+    pub(super) fn special_from_words(
+        board_width: u8,
+        words: &mut WordSource,
+        prefer: &[Preference],
+        rng: &mut impl Rng,
+    ) -> Option<Self> {
+        let kind = *Kind::SPECIAL.choose(rng).unwrap();
+        let block = if kind == Kind::Wildcard {
+            let x = rng.random_range(0..board_width);
+            Self::new(WILDCARD_TEXT, State::Interactable, x, 0)
+        } else {
+            Self::from_words(board_width, Shape::Bar, words, prefer, rng)?
+        };
+        Some(block.with_kind(kind))
+    }
+
+    /// A block in `shape` at a random column, with a word from `words` that fits the board, or
+    /// `None` if no word fits even as a bar. Words that meet every one of the `prefer`ences are
+    /// picked if any fit, or else ones that meet all but the last, and so on.
+    pub(super) fn from_words(
+        board_width: u8,
+        shape: Shape,
+        words: &mut WordSource,
+        prefer: &[Preference],
+        rng: &mut impl Rng,
+    ) -> Option<Self> {
+        let fits = |w: &str| {
+            u8::try_from(w.len())
+                .is_ok_and(|len| shape.fits(len) && shape.width(len) <= board_width)
+        };
+        let text = (0..=prefer.len())
+            .rev()
+            .find_map(|n| words.pick(|w| fits(w) && prefer[..n].iter().all(|p| p(w)), rng));
+        let Some(text) = text else {
+            // Nothing is short enough to take this shape on such a narrow board.
+            return if shape == Shape::Bar {
+                None
+            } else {
+                Self::from_words(board_width, Shape::Bar, words, prefer, rng)
+            };
         };
+        let mut ret = Self::new(text, State::Interactable, 0, 0).with_shape(shape);
         ret.position.x = rng.random_range(0..=(board_width - ret.width()));
-        ret
+        Some(ret)
     }
 
     #[inline]
-    pub(super) fn new(assigned_text: &'static str, state: State, x: u8, y: u8) -> Self {
+    pub(super) fn new(
+        assigned_text: impl Into<Cow<'static, str>>,
+        state: State,
+        x: u8,
+        y: u8,
+    ) -> Self {
         Self {
             state,
-            assigned_text,
+            assigned_text: assigned_text.into(),
             input_text: String::new(),
             position: BoardPosition { x, y },
+            shape: Shape::Bar,
+            orientation: Orientation::Horizontal,
+            kind: Kind::Normal,
+            unlocking: false,
+        }
+    }
+
+    /// A copy of this block with a random word shorter than its own, or `None` if there is no
+    /// shorter word that takes the same shape.
+    pub(super) fn shrunk(&self, rng: &mut impl Rng) -> Option<Self> {
+        if self.kind == Kind::Wildcard || self.is_garbage() {
+            return None;
         }
+        let text = *WORDS
+            .iter()
+            .filter(|w| w.len() < self.assigned_text.len() && self.shape.fits(w.len() as u8))
+            .choose(rng)?;
+        Some(Self {
+            assigned_text: text.into(),
+            input_text: String::new(),
+            ..self.clone()
+        })
+    }
+
+    #[inline]
+    pub(super) fn with_kind(mut self, kind: Kind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    #[inline]
+    pub(super) fn with_shape(mut self, shape: Shape) -> Self {
+        debug_assert!(shape.fits(self.assigned_text.len() as u8));
+        self.shape = shape;
+        self
+    }
+
+    /// A single settled filler cell, used to build garbage rows.
+    #[inline]
+    pub(super) fn garbage(x: u8, y: u8) -> Self {
+        Self::new(GARBAGE_TEXT, State::Settled, x, y)
+    }
+
+    #[inline]
+    pub fn is_garbage(&self) -> bool {
+        self.assigned_text == GARBAGE_TEXT
+    }
+
+    /// Whether `ch` is the right character to type at `index`.
+    #[inline]
+    pub fn matches(&self, index: usize, ch: char) -> bool {
+        self.kind == Kind::Wildcard || self.assigned_text[index..].starts_with(ch)
     }
 
     #[inline]
     pub fn is_correct(&self) -> bool {
-        self.assigned_text == self.input_text
+        self.input_text.len() == self.assigned_text.len()
+            && self
+                .input_text
+                .chars()
+                .enumerate()
+                .all(|(i, ch)| self.matches(i, ch))
+    }
+
+    /// Whether `ch` can be typed into this block at all, right or wrong. Letters always can, but
+    /// other characters only by words that have some in them, so that stray keys don't count as
+    /// mistakes.
+    fn accepts(&self, ch: char) -> bool {
+        ch.is_ascii_alphabetic()
+            || (ch.is_ascii_graphic()
+                && self.kind != Kind::Wildcard
+                && self.assigned_text.chars().any(|c| !c.is_ascii_alphabetic()))
     }
 
     #[inline]
     pub(super) fn add_char(&mut self, ch: char) -> bool {
-        if ch.is_ascii_alphabetic()
-            && self.is_interactable()
+        if self.accepts(ch)
+            && self.takes_input()
             && self.input_text.len() < self.assigned_text.len()
         {
             self.input_text.push(ch);
+            if self.kind == Kind::Ice && self.is_correct() {
+                self.kind = Kind::Normal;
+                self.input_text.clear();
+            }
             true
         } else {
             false
         }
     }
 
+    #[inline]
+    pub(super) fn clear_input(&mut self) {
+        self.input_text.clear();
+    }
+
     #[inline]
     pub(super) fn delete_char(&mut self) -> bool {
-        if self.is_interactable() {
+        if self.takes_input() {
             self.input_text.pop().is_some()
         } else {
             false
@@ -81,7 +366,114 @@ impl Block {
 
     #[inline]
     pub fn width(&self) -> u8 {
-        self.assigned_text.len() as u8
+        let len = self.assigned_text.len() as u8;
+        match self.orientation {
+            Orientation::Horizontal => self.shape.width(len),
+            Orientation::Vertical => self.shape.height(len),
+        }
+    }
+
+    #[inline]
+    pub fn height(&self) -> u8 {
+        let len = self.assigned_text.len() as u8;
+        match self.orientation {
+            Orientation::Horizontal => self.shape.height(len),
+            Orientation::Vertical => self.shape.width(len),
+        }
+    }
+
+    /// Positions of the cells of this block, in the order of the characters they show.
+    pub fn cells(&self) -> impl Iterator<Item = BoardPosition> {
+        let BoardPosition { x, y } = self.position;
+        let orientation = self.orientation;
+        self.shape
+            .rows(self.assigned_text.len() as u8)
+            .zip(0..)
+            .flat_map(|((offset, len), row)| (offset..offset + len).map(move |col| (col, row)))
+            .map(move |(col, row)| match orientation {
+                Orientation::Horizontal => BoardPosition {
+                    x: x + col,
+                    y: y + row,
+                },
+                Orientation::Vertical => BoardPosition {
+                    x: x + row,
+                    y: y + col,
+                },
+            })
+    }
+
+    /// Position of the cell showing the character at `index`, if there is one.
+    #[inline]
+    pub fn cell_position(&self, index: usize) -> Option<BoardPosition> {
+        self.cells().nth(index)
+    }
+
+    /// The rows of this block, from top to bottom.
+    pub fn segments(&self) -> impl Iterator<Item = Segment> {
+        let mut cells = self.cells().collect::<Vec<_>>();
+        cells.sort_by_key(|p| (p.y, p.x));
+        let mut segments: Vec<Segment> = Vec::with_capacity(self.height() as usize);
+        for cell in cells {
+            match segments.last_mut() {
+                Some(last) if last.position.y == cell.y && last.end_x() == cell.x => last.len += 1,
+                _ => segments.push(Segment {
+                    position: cell,
+                    len: 1,
+                }),
+            }
+        }
+        segments.into_iter()
+    }
+
+    /// Whether this block and `other` share a cell.
+    pub(super) fn overlaps(&self, other: &Self) -> bool {
+        self.segments().any(|a| {
+            other
+                .segments()
+                .any(|b| a.position.y == b.position.y && a.intersect_x(&b))
+        })
+    }
+
+    /// Break this block up into horizontal [`Shape::Bar`]s, one for each run of consecutive
+    /// characters on the same row, so every piece is one row high.
+    pub(super) fn into_rows(self) -> Vec<Self> {
+        if self.height() <= 1 {
+            return vec![self];
+        }
+        // (first character, one past the last character, position of the first character)
+        let mut runs: Vec<(usize, usize, BoardPosition)> = Vec::new();
+        for (i, cell) in self.cells().enumerate() {
+            match runs.last_mut() {
+                Some((start, end, first))
+                    if first.y == cell.y
+                        && first.x as usize + (*end - *start) == cell.x as usize =>
+                {
+                    *end = i + 1;
+                }
+                _ => runs.push((i, i + 1, cell)),
+            }
+        }
+        let typed = self.input_text.len();
+        runs.into_iter()
+            .map(|(start, end, position)| Self {
+                state: self.state,
+                // Borrowed from the same place if the whole text is borrowed.
+                assigned_text: match &self.assigned_text {
+                    Cow::Borrowed(text) => Cow::Borrowed(&text[start..end]),
+                    Cow::Owned(text) => Cow::Owned(text[start..end].to_string()),
+                },
+                input_text: self
+                    .input_text
+                    .get(start.min(typed)..end.min(typed))
+                    .unwrap_or_default()
+                    .to_string(),
+                position,
+                shape: Shape::Bar,
+                orientation: Orientation::Horizontal,
+                kind: self.kind,
+                unlocking: self.unlocking,
+            })
+            .collect()
     }
 
     #[inline]
@@ -99,18 +491,26 @@ impl Block {
         matches!(self.state, State::Falling)
     }
 
+    /// Whether this block is a landed [`Kind::Locked`] block that is waiting to be typed again.
+    #[inline]
+    pub fn is_unlocking(&self) -> bool {
+        self.unlocking
+    }
+
+    /// Whether typing goes to this block if it has focus.
+    #[inline]
+    pub fn takes_input(&self) -> bool {
+        self.is_interactable() || self.is_unlocking()
+    }
+
     #[inline]
     pub fn is_movable(&self) -> bool {
-        self.is_interactable() && self.is_correct()
+        self.is_interactable() && self.is_correct() && self.kind != Kind::Locked
     }
 
     #[inline]
-    pub(super) fn intersect_x(&self, other: &Self) -> bool {
-        let ax0 = self.position.x;
-        let ax1 = ax0 + self.width() - 1;
-        let bx0 = other.position.x;
-        let bx1 = bx0 + other.width() - 1;
-        !(ax1 < bx0 || bx1 < ax0)
+    pub fn assigned_text(&self) -> &str {
+        &self.assigned_text
     }
 }
 
@@ -148,7 +548,7 @@ mod test {
         if width == 0 {
             return true;
         }
-        let b = Block::random(width);
+        let b = Block::random(width, &mut rand::rng());
         assert!(!b.assigned_text.is_empty());
         assert!(b.assigned_text.is_ascii());
         assert!(b.assigned_text.len() <= width as usize);
@@ -159,22 +559,141 @@ mod test {
         true
     }
 
+    #[quickcheck]
+    fn new_shaped(width: u8) -> bool {
+        if width == 0 {
+            return true;
+        }
+        let b = Block::random_shaped(width, &mut rand::rng());
+        assert!(b.shape.fits(b.assigned_text.len() as u8));
+        assert!(b.position.x + b.width() <= width);
+        assert_eq!(
+            b.segments().map(|s| s.len as usize).sum::<usize>(),
+            b.assigned_text.len()
+        );
+        true
+    }
+
+    #[test]
+    fn shapes() {
+        let cells = |shape: Shape| {
+            let b = Block::new_settled("planet", 1, 2).with_shape(shape);
+            (0..6)
+                .map(|i| {
+                    let p = b.cell_position(i).unwrap();
+                    (p.x, p.y)
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            cells(Shape::Bar),
+            [(1, 2), (2, 2), (3, 2), (4, 2), (5, 2), (6, 2)]
+        );
+        assert_eq!(
+            cells(Shape::O),
+            [(1, 2), (2, 2), (3, 2), (1, 3), (2, 3), (3, 3)]
+        );
+        assert_eq!(
+            cells(Shape::S),
+            [(2, 2), (3, 2), (4, 2), (1, 3), (2, 3), (3, 3)]
+        );
+        assert_eq!(
+            cells(Shape::Z),
+            [(1, 2), (2, 2), (3, 2), (2, 3), (3, 3), (4, 3)]
+        );
+        assert_eq!(
+            cells(Shape::L),
+            [(1, 2), (1, 3), (2, 3), (3, 3), (4, 3), (5, 3)]
+        );
+        assert_eq!(
+            cells(Shape::J),
+            [(5, 2), (1, 3), (2, 3), (3, 3), (4, 3), (5, 3)]
+        );
+        assert_eq!(
+            cells(Shape::T),
+            [(1, 2), (2, 2), (3, 2), (4, 2), (5, 2), (3, 3)]
+        );
+        assert!(!Shape::O.fits(5));
+        assert!(!Shape::T.fits(2));
+
+        let mut b = Block::new_falling("planet", 1, 2).with_shape(Shape::L);
+        b.input_text = "plan".to_string();
+        assert_eq!((b.width(), b.height()), (5, 2));
+        let rows = b.into_rows();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(
+            (rows[0].assigned_text(), rows[0].input_text.as_str()),
+            ("p", "p")
+        );
+        assert_eq!(
+            (rows[1].assigned_text(), rows[1].input_text.as_str()),
+            ("lanet", "lan")
+        );
+        assert_eq!(rows[1].position, BoardPosition { x: 1, y: 3 });
+
+        // pl
+        //  a
+        //  n
+        //  e
+        //  t
+        let mut b = Block::new_settled("planet", 1, 2).with_shape(Shape::L);
+        b.orientation = Orientation::Vertical;
+        assert_eq!((b.width(), b.height()), (2, 5));
+        assert_eq!(b.cell_position(0), Some(BoardPosition { x: 1, y: 2 }));
+        assert_eq!(b.cell_position(2), Some(BoardPosition { x: 2, y: 3 }));
+        assert_eq!(b.cell_position(6), None);
+        assert_eq!(
+            b.into_rows()
+                .iter()
+                .map(|b| (b.assigned_text(), b.position.x, b.position.y))
+                .collect::<Vec<_>>(),
+            [
+                ("pl", 1, 2),
+                ("a", 2, 3),
+                ("n", 2, 4),
+                ("e", 2, 5),
+                ("t", 2, 6)
+            ]
+        );
+    }
+
+    #[test]
+    fn kinds() {
+        let mut b = Block::new_interactable(WILDCARD_TEXT, 0, 0).with_kind(Kind::Wildcard);
+        assert!(!b.add_char('?'));
+        assert!(b.add_char('q'));
+        assert!(b.is_correct());
+
+        let mut b = Block::new_interactable("ab", 0, 0).with_kind(Kind::Ice);
+        assert!(!b.matches(0, 'b'));
+        assert!(b.add_char('a'));
+        assert!(b.add_char('b'));
+        assert_eq!(b.kind, Kind::Normal);
+        assert!(!b.is_correct());
+        assert!(b.add_char('a'));
+        assert!(b.add_char('b'));
+        assert!(b.is_movable());
+
+        let b = Block::new_settled("ab", 0, 0).with_kind(Kind::Bomb);
+        assert_eq!(b.into_rows()[0].kind, Kind::Bomb);
+    }
+
     #[test]
     fn intersect() {
-        assert!(Block::with_text_x("abc", 0).intersect_x(&Block::with_text_x("abc", 0)));
-        assert!(Block::with_text_x("abc", 1).intersect_x(&Block::with_text_x("abc", 0)));
-        assert!(Block::with_text_x("abc", 0).intersect_x(&Block::with_text_x("abc", 1)));
-        assert!(Block::with_text_x("abc", 2).intersect_x(&Block::with_text_x("abc", 0)));
-        assert!(Block::with_text_x("abc", 0).intersect_x(&Block::with_text_x("abc", 2)));
-        assert!(Block::with_text_x("abcdef", 0).intersect_x(&Block::with_text_x("abc", 1)));
-        assert!(Block::with_text_x("abc", 2).intersect_x(&Block::with_text_x("abcdefg", 0)));
-        assert!(!Block::with_text_x("abc", 0).intersect_x(&Block::with_text_x("abc", 3)));
-        assert!(!Block::with_text_x("abc", 0).intersect_x(&Block::with_text_x("abcdefg", 6)));
-        assert!(!Block::with_text_x("abc", 3).intersect_x(&Block::with_text_x("abc", 0)));
-        assert!(!Block::with_text_x("abcdef", 10).intersect_x(&Block::with_text_x("abcdefg", 42)));
-        assert!(!Block::with_text_x("abcdef", 100).intersect_x(&Block::with_text_x("abcdefg", 42)));
-        assert!(!Block::with_text_x("a", 100).intersect_x(&Block::with_text_x("b", 101)));
-        assert!(Block::with_text_x("a", 100).intersect_x(&Block::with_text_x("b", 100)));
+        assert!(Block::with_text_x("abc", 0).overlaps(&Block::with_text_x("abc", 0)));
+        assert!(Block::with_text_x("abc", 1).overlaps(&Block::with_text_x("abc", 0)));
+        assert!(Block::with_text_x("abc", 0).overlaps(&Block::with_text_x("abc", 1)));
+        assert!(Block::with_text_x("abc", 2).overlaps(&Block::with_text_x("abc", 0)));
+        assert!(Block::with_text_x("abc", 0).overlaps(&Block::with_text_x("abc", 2)));
+        assert!(Block::with_text_x("abcdef", 0).overlaps(&Block::with_text_x("abc", 1)));
+        assert!(Block::with_text_x("abc", 2).overlaps(&Block::with_text_x("abcdefg", 0)));
+        assert!(!Block::with_text_x("abc", 0).overlaps(&Block::with_text_x("abc", 3)));
+        assert!(!Block::with_text_x("abc", 0).overlaps(&Block::with_text_x("abcdefg", 6)));
+        assert!(!Block::with_text_x("abc", 3).overlaps(&Block::with_text_x("abc", 0)));
+        assert!(!Block::with_text_x("abcdef", 10).overlaps(&Block::with_text_x("abcdefg", 42)));
+        assert!(!Block::with_text_x("abcdef", 100).overlaps(&Block::with_text_x("abcdefg", 42)));
+        assert!(!Block::with_text_x("a", 100).overlaps(&Block::with_text_x("b", 101)));
+        assert!(Block::with_text_x("a", 100).overlaps(&Block::with_text_x("b", 100)));
     }
 
     mod add_del_char {
@@ -184,9 +703,13 @@ mod test {
         fn make_block(text: &'static str, state: State) -> Block {
             Block {
                 state,
-                assigned_text: text,
+                assigned_text: text.into(),
                 input_text: String::new(),
                 position: BoardPosition { x: 0, y: 0 },
+                shape: Shape::Bar,
+                orientation: Orientation::Horizontal,
+                kind: Kind::Normal,
+                unlocking: false,
             }
         }
 
@@ -225,6 +748,18 @@ mod test {
             add_and_assert(&mut b, '0', false, false, "");
         }
 
+        #[test]
+        fn punctuation() {
+            let mut b = make_block("4th,", State::Interactable);
+            add_and_assert(&mut b, '4', true, false, "4");
+            add_and_assert(&mut b, 't', true, false, "4t");
+            add_and_assert(&mut b, '.', true, false, "4t.");
+            delete_and_assert(&mut b, true, false, "4t");
+            add_and_assert(&mut b, 'h', true, false, "4th");
+            add_and_assert(&mut b, ' ', false, false, "4th");
+            add_and_assert(&mut b, ',', true, true, "4th,");
+        }
+
         #[test]
         fn t0() {
             let mut b = make_block("a", State::Interactable);
@@ -273,9 +808,13 @@ mod test {
             for state in [State::Falling, State::Settled] {
                 let a = Block {
                     state,
-                    assigned_text: "abc",
+                    assigned_text: "abc".into(),
                     input_text: "a".to_string(),
                     position: BoardPosition { x: 0, y: 0 },
+                    shape: Shape::Bar,
+                    orientation: Orientation::Horizontal,
+                    kind: Kind::Normal,
+                    unlocking: false,
                 };
                 let mut b = a.clone();
                 assert!(!b.delete_char());
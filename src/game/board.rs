@@ -1,12 +1,15 @@
 use std::cmp::Ordering;
 
 use getset::{CopyGetters, Getters};
+use rand::{Rng, seq::IndexedRandom};
 
 use super::Block;
-use super::block::State as BlockState;
+use super::block::{Kind, Shape, State as BlockState};
+use super::settings::Settings;
+use super::words::{Preference, WordSource};
 
 /// A position on the board. Origin is top left.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub struct BoardPosition {
     pub x: u8,
     pub y: u8,
@@ -78,14 +81,57 @@ impl AnchorCoordinates {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Msg {
     GameOver,
-    BlocksSettled,
+    /// The blocks that settled, as they were before being split into rows.
+    BlocksSettled(Vec<Block>),
     Updated,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, CopyGetters, Getters)]
+/// A run of empty cells side by side in one row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Gap {
+    /// The leftmost cell's column.
+    pub x: u8,
+    pub y: u8,
+    pub width: u8,
+}
+
+impl Gap {
+    /// The x just past the rightmost cell.
+    #[inline]
+    pub fn end_x(&self) -> u8 {
+        self.x + self.width
+    }
+}
+
+/// Which lengths of words spawn, going by the [`Board::open_gaps`] in the stack. Words are
+/// measured lying flat, as they'd be before being wrapped into a shape or turned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WordSizing {
+    /// Any length.
+    #[default]
+    Any,
+    /// Words just as long as a gap, that fill it when dropped in, to help keep the stack tidy.
+    FillGaps,
+    /// Words that don't fill any gap, to make the stack harder to keep tidy.
+    AvoidGaps,
+}
+
+impl WordSizing {
+    /// Whether a word `len` characters long suits a stack with `gaps`.
+    pub fn suits(self, len: usize, gaps: &[Gap]) -> bool {
+        let fills = gaps.iter().any(|gap| gap.width as usize == len);
+        match self {
+            Self::Any => true,
+            Self::FillGaps => fills,
+            Self::AvoidGaps => !fills,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, CopyGetters, Getters)]
 pub struct Board {
     #[getset(get = "pub")]
     blocks: Vec<Block>,
@@ -97,13 +143,9 @@ pub struct Board {
 
 impl Board {
     #[inline]
-    pub fn new(width: u8, height: u8, starts_with_one: bool) -> Self {
+    pub fn new(width: u8, height: u8) -> Self {
         Self {
-            blocks: if starts_with_one {
-                vec![Block::random(width)]
-            } else {
-                vec![]
-            },
+            blocks: vec![],
             width,
             height,
         }
@@ -111,27 +153,177 @@ impl Board {
 
     /// Find the max y of the block at `target_index` to prevent overlapping with other blocks
     /// below it.
+    #[inline]
     pub(super) fn find_max_y(&self, target_index: usize) -> u8 {
-        let target_block = &self.blocks[target_index];
+        self.landing_y(&self.blocks[target_index])
+    }
+
+    /// Find the max y `target` can fall to without overlapping any settled block below it.
+    /// `target` doesn't need to be on the board. A piece taller than the board can only be at the
+    /// top, which tops out the stack.
+    pub(super) fn landing_y(&self, target: &Block) -> u8 {
+        let top = target.position.y;
         self.blocks
             .iter()
-            .enumerate()
-            .filter_map(|(i, block)| {
-                (i != target_index
-                    && block.is_settled()
-                    && block.position.y > target_block.position.y
-                    && block.intersect_x(target_block))
-                .then_some(block.position.y)
+            .filter(|block| block.is_settled() && !std::ptr::eq(*block, target))
+            .flat_map(|block| block.segments())
+            .flat_map(|obstacle| {
+                target
+                    .segments()
+                    .filter(move |segment| {
+                        obstacle.position.y > segment.position.y && obstacle.intersect_x(segment)
+                    })
+                    .map(move |segment| obstacle.position.y - (segment.position.y - top))
             })
             .min()
-            .unwrap_or(self.height)
-            - 1
+            .map_or(self.height.saturating_sub(target.height()), |y| y - 1)
     }
 
-    /// Clear completed rows and return the number of rows cleared.
+    /// Whether each cell has a settled block in it, by row and then column.
+    fn filled_cells(&self) -> Vec<Vec<bool>> {
+        let mut ret = vec![vec![false; self.width as usize]; self.height as usize];
+        for cell in self
+            .blocks
+            .iter()
+            .filter(|b| b.is_settled())
+            .flat_map(|b| b.cells())
+        {
+            if let Some(filled) = ret
+                .get_mut(cell.y as usize)
+                .and_then(|row| row.get_mut(cell.x as usize))
+            {
+                *filled = true;
+            }
+        }
+        ret
+    }
+
+    /// How high the stack is in each column, from the floor to the top of its highest settled
+    /// cell, holes and all.
+    pub fn column_heights(&self) -> Vec<u8> {
+        let filled = self.filled_cells();
+        (0..self.width as usize)
+            .map(|x| {
+                (0..self.height)
+                    .find(|&y| filled[y as usize][x])
+                    .map_or(0, |top| self.height - top)
+            })
+            .collect()
+    }
+
+    /// Empty cells with a settled cell somewhere above them in the same column, from the top row
+    /// down and left to right.
+    pub fn holes(&self) -> Vec<BoardPosition> {
+        let filled = self.filled_cells();
+        let heights = self.column_heights();
+        (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| BoardPosition { x, y }))
+            .filter(|&BoardPosition { x, y }| {
+                !filled[y as usize][x as usize] && y >= self.height - heights[x as usize]
+            })
+            .collect()
+    }
+
+    /// How many cells of each row settled blocks take up, from the top row down.
+    pub fn filled_per_row(&self) -> Vec<usize> {
+        self.filled_cells()
+            .iter()
+            .map(|row| row.iter().filter(|&&filled| filled).count())
+            .collect()
+    }
+
+    /// The runs of empty cells in row `y`, from left to right.
+    pub fn row_gaps(&self, y: u8) -> Vec<Gap> {
+        let Some(row) = self.filled_cells().into_iter().nth(y as usize) else {
+            return Vec::new();
+        };
+        let mut ret: Vec<Gap> = Vec::new();
+        for (x, filled) in (0..self.width).zip(row) {
+            match ret.last_mut() {
+                _ if filled => (),
+                Some(gap) if gap.end_x() == x => gap.width += 1,
+                _ => ret.push(Gap { x, y, width: 1 }),
+            }
+        }
+        ret
+    }
+
+    /// How much the stack's height changes from each column to the next, added up. A flat stack
+    /// scores 0.
+    pub fn bumpiness(&self) -> usize {
+        self.column_heights()
+            .windows(2)
+            .map(|w| w[0].abs_diff(w[1]) as usize)
+            .sum()
+    }
+
+    /// Whether every empty cell of row `y` can still be got to by a block coming down from the
+    /// top, falling and sliding around settled blocks, so the row can be completed.
+    pub fn can_complete_row(&self, y: u8) -> bool {
+        let filled = self.filled_cells();
+        let Some(row) = filled.get(y as usize) else {
+            return false;
+        };
+        let reachable = self.reachable_cells(&filled);
+        row.iter()
+            .zip(&reachable[y as usize])
+            .all(|(&filled, &reachable)| filled || reachable)
+    }
+
+    /// Which empty cells a block coming down from the top could get to, moving down, left and
+    /// right through empty cells.
+    fn reachable_cells(&self, filled: &[Vec<bool>]) -> Vec<Vec<bool>> {
+        let (width, height) = (self.width as usize, self.height as usize);
+        let mut ret = vec![vec![false; width]; height];
+        let mut stack: Vec<(usize, usize)> = (0..width).map(|x| (x, 0)).collect();
+        while let Some((x, y)) = stack.pop() {
+            if y >= height || x >= width || filled[y][x] || ret[y][x] {
+                continue;
+            }
+            ret[y][x] = true;
+            stack.push((x, y + 1));
+            stack.push((x + 1, y));
+            if let Some(left) = x.checked_sub(1) {
+                stack.push((left, y));
+            }
+        }
+        ret
+    }
+
+    /// The row a single cell dropped down each column would land in, or `None` if the column is
+    /// full to the top.
+    fn landing_rows(&self) -> Vec<Option<u8>> {
+        self.column_heights()
+            .into_iter()
+            .map(|h| (self.height - h).checked_sub(1))
+            .collect()
+    }
+
+    /// Gaps in the top of the stack that a word lying flat can be dropped straight into and fill:
+    /// runs of columns side by side that it would land in the same row of. On an empty board,
+    /// that's the whole bottom row.
+    pub fn open_gaps(&self) -> Vec<Gap> {
+        let mut ret: Vec<Gap> = Vec::new();
+        for (x, y) in self.landing_rows().into_iter().enumerate() {
+            let (Some(y), Ok(x)) = (y, u8::try_from(x)) else {
+                continue;
+            };
+            match ret.last_mut() {
+                Some(gap) if gap.y == y && gap.end_x() == x => gap.width += 1,
+                _ => ret.push(Gap { x, y, width: 1 }),
+            }
+        }
+        ret
+    }
+
+    /// Clear completed rows, and the rows blown up by bombs that have landed, and return the
+    /// blocks that were cleared. A completed row with a locked block in it isn't cleared; the
+    /// locked block takes focus instead.
     pub(super) fn clear_completed(&mut self) -> Vec<Block> {
+        self.split_settled();
         self.sort();
         let mut removals = Vec::new();
+        let mut locked_rows = Vec::new();
         for chunk in self
             .blocks
             .chunk_by_mut(|a, b| a.position.y == b.position.y)
@@ -162,14 +354,52 @@ impl Board {
                 && let Some(b) = chunk.last()
                 && b.position.x + b.width() == self.width
             {
-                removals.push(b.position.y);
+                if chunk.iter().any(|b| b.kind == Kind::Locked) {
+                    locked_rows.push(b.position.y);
+                } else {
+                    removals.push(b.position.y);
+                }
+            }
+        }
+
+        for bomb in self
+            .blocks
+            .iter()
+            .filter(|b| b.is_settled() && b.kind == Kind::Bomb)
+        {
+            let y = bomb.position.y;
+            removals.extend(y.saturating_sub(1)..=(y + 1).min(self.height - 1));
+        }
+        removals.sort_unstable();
+        removals.dedup();
+
+        for block in self.blocks.iter_mut() {
+            if block.is_settled()
+                && block.kind == Kind::Locked
+                && locked_rows.contains(&block.position.y)
+                && !removals.contains(&block.position.y)
+            {
+                block.unlocking = true;
+                block.clear_input();
             }
         }
 
-        if let Some(&max_y) = removals.first() {
+        self.remove_rows(&removals)
+    }
+
+    /// Clear row `y`, full or not, and return the blocks that were cleared.
+    pub(super) fn clear_row(&mut self, y: u8) -> Vec<Block> {
+        self.split_settled();
+        self.remove_rows(&[y])
+    }
+
+    /// Remove every block on `rows`, which are in ascending order, and let the settled blocks
+    /// above them fall.
+    fn remove_rows(&mut self, rows: &[u8]) -> Vec<Block> {
+        if let Some(&max_y) = rows.last() {
             self.blocks
                 .extract_if(.., |b| {
-                    if removals.contains(&b.position.y) {
+                    if rows.contains(&b.position.y) {
                         return true;
                     }
                     if b.is_settled() && b.position.y < max_y {
@@ -184,7 +414,7 @@ impl Board {
     }
 
     pub(super) fn fall_tick(&mut self, include_interactable: bool) -> Option<Msg> {
-        let mut newly_settled = false;
+        let mut newly_settled = vec![];
         let mut has_update = false;
         for i in 0..self.blocks.len() {
             let block = &self.blocks[i];
@@ -195,7 +425,11 @@ impl Board {
             let block = &mut self.blocks[i];
             if block.position.y == max_y {
                 block.state = BlockState::Settled;
-                newly_settled = true;
+                if block.kind == Kind::Locking {
+                    block.kind = Kind::Locked;
+                    block.clear_input();
+                }
+                newly_settled.push(block.clone());
                 if max_y == 0 {
                     return Some(Msg::GameOver);
                 }
@@ -204,37 +438,131 @@ impl Board {
                 has_update = true;
             }
         }
-        if newly_settled {
-            Some(Msg::BlocksSettled)
+        if !newly_settled.is_empty() {
+            self.split_settled();
+            Some(Msg::BlocksSettled(newly_settled))
         } else {
             has_update.then_some(Msg::Updated)
         }
     }
 
+    /// The block typing goes to: a locked block waiting to be typed again if there is one, and
+    /// the block in play otherwise.
     #[inline]
     pub fn get_focused_index(&self) -> Option<usize> {
-        self.blocks.iter().position(|b| b.is_interactable())
+        self.blocks
+            .iter()
+            .position(|b| b.is_unlocking())
+            .or_else(|| self.blocks.iter().position(|b| b.is_interactable()))
     }
 
     #[inline]
     pub fn get_focused(&self) -> Option<&Block> {
-        self.blocks.iter().find(|b| b.is_interactable())
+        self.get_focused_index().map(|i| &self.blocks[i])
     }
 
     #[inline]
     pub(super) fn get_focused_mut(&mut self) -> Option<&mut Block> {
-        self.blocks.iter_mut().find(|b| b.is_interactable())
+        self.get_focused_index().map(|i| &mut self.blocks[i])
     }
 
+    /// Throw away the focused block. A locked block waiting to be typed is part of the stack, so
+    /// it can't be thrown away.
     #[inline]
-    pub(super) fn spawn_block(&mut self) {
-        self.blocks.push(Block::random(self.width));
+    pub(super) fn remove_focused(&mut self) -> Option<Block> {
+        let index = self.get_focused_index()?;
+        if self.blocks[index].is_unlocking() {
+            return None;
+        }
+        Some(self.blocks.remove(index))
+    }
+
+    /// Swap the focused block's word for a shorter one, if one fits where it is.
+    pub(super) fn shrink_focused(&mut self, rng: &mut impl Rng) -> bool {
+        let Some(index) = self.get_focused_index() else {
+            return false;
+        };
+        let Some(shrunk) = self.blocks[index].shrunk(rng) else {
+            return false;
+        };
+        if self
+            .blocks
+            .iter()
+            .any(|b| b.is_settled() && b.overlaps(&shrunk))
+        {
+            return false;
+        }
+        self.blocks[index] = shrunk;
+        true
     }
 
+    /// Spawn a word from `words` at the top, meeting as many of the `prefer`ences as it can, most
+    /// important first. It's wrapped into a random [`Shape`](super::block::Shape) if the settings
+    /// allow, and some are special blocks. Returns whether anything spawned, which it doesn't once
+    /// `words` have run out.
+    pub(super) fn spawn_block(
+        &mut self,
+        rng: &mut impl Rng,
+        settings: &Settings,
+        words: &mut WordSource,
+        prefer: &[Preference],
+    ) -> bool {
+        if words.is_exhausted() {
+            return false;
+        }
+        let special =
+            settings.special_block_chance > 0.0 && rng.random_bool(settings.special_block_chance);
+        let block = if special {
+            Block::special_from_words(self.width, words, prefer, rng)
+        } else if settings.shaped_pieces {
+            let shape = *Shape::ALL.choose(rng).unwrap();
+            Block::from_words(self.width, shape, words, prefer, rng)
+        } else {
+            Block::from_words(self.width, Shape::Bar, words, prefer, rng)
+        };
+        let Some(block) = block else {
+            return false;
+        };
+        self.blocks.push(block);
+        true
+    }
+
+    /// Break settled blocks up into their rows. Once a piece has landed, each of its rows is part
+    /// of a different row of the stack, and is cleared and falls on its own.
+    fn split_settled(&mut self) {
+        if self
+            .blocks
+            .iter()
+            .all(|b| !b.is_settled() || b.height() == 1)
+        {
+            return;
+        }
+        self.blocks = std::mem::take(&mut self.blocks)
+            .into_iter()
+            .flat_map(|b| {
+                if b.is_settled() {
+                    b.into_rows()
+                } else {
+                    vec![b]
+                }
+            })
+            .collect();
+    }
+
+    /// Drop the focused block. A locked block that's waiting to be typed is already part of the
+    /// stack, so it stays where it is, unlocked if it was typed correctly.
     #[inline]
     pub(super) fn focus_next(&mut self) -> bool {
         if let Some(focus) = self.get_focused_mut() {
-            focus.state = BlockState::Falling;
+            if focus.is_unlocking() {
+                if focus.is_correct() {
+                    focus.kind = Kind::Normal;
+                }
+                focus.clear_input();
+                focus.unlocking = false;
+            } else {
+                focus.state = BlockState::Falling;
+            }
             true
         } else {
             false
@@ -249,16 +577,19 @@ impl Board {
         if !focus.is_movable() {
             return false;
         }
-        let x = focus.position.x;
-        if x == 0 {
+        let blocked = focus.segments().any(|segment| {
+            let BoardPosition { x, y } = segment.position;
+            x == 0
+                || self
+                    .blocks
+                    .iter()
+                    .take_while(|b| b.is_settled())
+                    .flat_map(|b| b.segments())
+                    .any(|s| s.position.y == y && s.end_x() == x)
+        });
+        if blocked {
             return false;
         }
-        let y = focus.position.y;
-        for block in self.blocks.iter().take_while(|b| b.is_settled()) {
-            if block.position.y == y && block.position.x + block.width() == x {
-                return false;
-            }
-        }
         self.blocks[focus_index].position.x -= 1;
         true
     }
@@ -271,20 +602,108 @@ impl Board {
         if !focus.is_movable() {
             return false;
         };
-        let x = focus.position.x + focus.width();
-        if x == self.width {
+        let blocked = focus.segments().any(|segment| {
+            let (x, y) = (segment.end_x(), segment.position.y);
+            x == self.width
+                || self
+                    .blocks
+                    .iter()
+                    .take_while(|b| b.is_settled())
+                    .flat_map(|b| b.segments())
+                    .any(|s| s.position.y == y && s.position.x == x)
+        });
+        if blocked {
             return false;
         }
-        let y = focus.position.y;
-        for block in self.blocks.iter().take_while(|b| b.is_settled()) {
-            if block.position.y == y && block.position.x == x {
-                return false;
-            }
-        }
         self.blocks[focus_index].position.x += 1;
         true
     }
 
+    /// Turn the focused block between horizontal and vertical. If it doesn't fit where it is, it's
+    /// nudged sideways, then upwards, to the nearest place it does fit.
+    pub(super) fn rotate(&mut self) -> bool {
+        let Some(focus_index) = self.get_focused_index() else {
+            return false;
+        };
+        let focus = &self.blocks[focus_index];
+        if !focus.is_movable() {
+            return false;
+        }
+        let mut rotated = focus.clone();
+        rotated.orientation = rotated.orientation.rotated();
+        let BoardPosition { x, y } = focus.position;
+        let reach = rotated.width().max(rotated.height()) as i16;
+        let kicks =
+            (0..=reach).flat_map(|dy| (0..=reach).flat_map(move |dx| [(-dx, -dy), (dx, -dy)]));
+        for (dx, dy) in kicks {
+            let (Ok(x), Ok(y)) = (u8::try_from(x as i16 + dx), u8::try_from(y as i16 + dy)) else {
+                continue;
+            };
+            if x as u16 + rotated.width() as u16 > self.width as u16
+                || y as u16 + rotated.height() as u16 > self.height as u16
+            {
+                continue;
+            }
+            rotated.position = BoardPosition { x, y };
+            if !self
+                .blocks
+                .iter()
+                .any(|b| b.is_settled() && b.overlaps(&rotated))
+            {
+                self.blocks[focus_index] = rotated;
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Push the settled stack up by `rows` and fill the freed rows at the bottom with garbage,
+    /// leaving a single empty cell at column `gap` in each of them. Blocks that are still in the
+    /// air are pushed up as well if the rising stack runs into them.
+    pub(super) fn add_garbage(&mut self, rows: u8, gap: u8) -> Option<Msg> {
+        if rows == 0 {
+            return None;
+        }
+        let mut topped_out = false;
+        for block in self.blocks.iter_mut().filter(|b| b.is_settled()) {
+            if block.position.y < rows {
+                topped_out = true;
+                block.position.y = 0;
+            } else {
+                block.position.y -= rows;
+            }
+        }
+        for y in self.height - rows.min(self.height)..self.height {
+            for x in (0..self.width).filter(|&x| x != gap) {
+                self.blocks.push(Block::garbage(x, y));
+            }
+        }
+        self.sort();
+
+        for i in 0..self.blocks.len() {
+            if self.blocks[i].is_settled() {
+                continue;
+            }
+            while self
+                .blocks
+                .iter()
+                .any(|b| b.is_settled() && b.overlaps(&self.blocks[i]))
+            {
+                if self.blocks[i].position.y == 0 {
+                    topped_out = true;
+                    break;
+                }
+                self.blocks[i].position.y -= 1;
+            }
+        }
+
+        if topped_out {
+            Some(Msg::GameOver)
+        } else {
+            Some(Msg::Updated)
+        }
+    }
+
     #[inline]
     pub(super) fn sort(&mut self) {
         self.blocks.sort_by(|a, b| match a.state.cmp(&b.state) {
@@ -328,6 +747,7 @@ impl Board {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::game::block::Orientation;
 
     #[inline]
     fn assert_unchanged(a: &Board, f: impl FnOnce(&mut Board) -> bool) {
@@ -520,7 +940,7 @@ mod test {
             assert_eq!(board.get_focused_index(), Some(i));
 
             assert_eq!(board.fall_tick(true), Some(Msg::Updated));
-            assert_eq!(board.fall_tick(true), Some(Msg::BlocksSettled));
+            assert!(matches!(board.fall_tick(true), Some(Msg::BlocksSettled(_))));
             assert_eq!(board.blocks[i].position, BoardPosition { x: 2, y: 22 });
             assert_eq!(board.get_focused_index(), None);
 
@@ -544,7 +964,7 @@ mod test {
             assert_eq!(board.blocks[i + 1].position, BoardPosition { x: 7, y: 4 });
             assert_eq!(board.get_focused_index(), Some(i));
 
-            assert_eq!(board.fall_tick(true), Some(Msg::BlocksSettled));
+            assert!(matches!(board.fall_tick(true), Some(Msg::BlocksSettled(_))));
             assert_eq!(board.blocks[i].position, BoardPosition { x: 2, y: 22 });
             assert_eq!(board.blocks[i + 1].position, BoardPosition { x: 7, y: 5 });
             assert_eq!(board.get_focused_index(), Some(i + 1));
@@ -562,7 +982,7 @@ mod test {
             board.blocks.push(block(2, 0, "Bayanetta"));
             assert_eq!(board.fall_tick(true), Some(Msg::Updated));
             assert_eq!(board.blocks[i].position, BoardPosition { x: 2, y: 1 });
-            assert_eq!(board.fall_tick(true), Some(Msg::BlocksSettled));
+            assert!(matches!(board.fall_tick(true), Some(Msg::BlocksSettled(_))));
             assert_eq!(board.blocks[i].position, BoardPosition { x: 2, y: 1 });
 
             let i = board.blocks.len();
@@ -573,11 +993,17 @@ mod test {
 
         #[test]
         fn settle_bottom() {
-            let mut board = Board::new(4, 4, true);
+            let mut board = Board::new(4, 4);
+            board.spawn_block(
+                &mut rand::rng(),
+                &Settings::default(),
+                &mut WordSource::default(),
+                &[],
+            );
             assert_eq!(board.fall_tick(true), Some(Msg::Updated));
             assert_eq!(board.fall_tick(true), Some(Msg::Updated));
             assert_eq!(board.fall_tick(true), Some(Msg::Updated));
-            assert_eq!(board.fall_tick(true), Some(Msg::BlocksSettled));
+            assert!(matches!(board.fall_tick(true), Some(Msg::BlocksSettled(_))));
             assert_unchanged(&board, drift);
         }
     }
@@ -620,6 +1046,32 @@ mod test {
         }
     }
 
+    #[test]
+    fn garbage() {
+        let mut board = Board {
+            blocks: vec![Block::new_settled("abc", 0, 7), block(1, 6, "de")],
+            width: 4,
+            height: 8,
+        };
+        assert_unchanged(&board, |b| b.add_garbage(0, 0).is_some());
+        assert_eq!(board.add_garbage(2, 1), Some(Msg::Updated));
+        assert_eq!(
+            board.blocks,
+            vec![
+                Block::garbage(0, 7),
+                Block::garbage(2, 7),
+                Block::garbage(3, 7),
+                Block::garbage(0, 6),
+                Block::garbage(2, 6),
+                Block::garbage(3, 6),
+                Block::new_settled("abc", 0, 5),
+                block(1, 4, "de"),
+            ]
+        );
+        assert!(board.clear_completed().is_empty());
+        assert_eq!(board.add_garbage(5, 0), Some(Msg::GameOver));
+    }
+
     #[test]
     fn find_max_y() {
         let mut board = Board::populated();
@@ -631,4 +1083,295 @@ mod test {
         board.blocks.push(block(7, 2, "Hornet"));
         assert_eq!(board.find_max_y(i + 1), 22);
     }
+
+    #[test]
+    fn landing_extremes() {
+        let tall = Board::new(4, u8::MAX);
+        assert_eq!(tall.landing_y(&Block::new_falling("ab", 0, 0)), u8::MAX - 1);
+
+        // A piece taller than the board can't fall at all.
+        let mut planet = Block::new_falling("planet", 0, 0);
+        planet.orientation = Orientation::Vertical;
+        assert_eq!(Board::new(4, 4).landing_y(&planet), 0);
+    }
+
+    #[test]
+    fn shaped() {
+        use crate::game::block::Shape;
+
+        // ..........
+        // ....s.....
+        // stuv......
+        // ab.....cd.
+        let mut board = Board {
+            blocks: vec![
+                Block::new_settled("ab", 0, 3),
+                Block::new_settled("cd", 7, 3),
+                Block::new_settled("stuv", 0, 2),
+                Block::new_settled("s", 4, 1),
+                Block::new_interactable("planet", 3, 0).with_shape(Shape::Z),
+            ],
+            width: 10,
+            height: 4,
+        };
+        // pla
+        //  net
+        // The top row of the Z catches on "s" unless the bottom row catches on "cd" first.
+        assert_eq!(board.find_max_y(4), 0);
+        for ch in "planet".chars() {
+            board.get_focused_mut().unwrap().add_char(ch);
+        }
+        assert!(board.right());
+        assert_eq!(board.find_max_y(4), 0);
+        assert!(board.right());
+        assert_eq!(board.find_max_y(4), 1);
+        assert!(board.right());
+        assert!(!board.right());
+        assert!(board.left());
+
+        // Only the top row is next to "s".
+        board.blocks[4].position.y = 1;
+        assert!(!board.left());
+
+        // The rows of the Z settle as separate blocks.
+        assert!(board.focus_next());
+        assert!(matches!(board.fall_tick(true), Some(Msg::BlocksSettled(_))));
+        assert_eq!(board.blocks.len(), 6);
+        assert!(
+            board
+                .blocks
+                .iter()
+                .all(|b| b.is_settled() && b.height() == 1)
+        );
+        assert_eq!(board.blocks[4].assigned_text(), "pla");
+        assert_eq!(board.blocks[4].position, BoardPosition { x: 5, y: 1 });
+        assert_eq!(board.blocks[5].assigned_text(), "net");
+        assert_eq!(board.blocks[5].position, BoardPosition { x: 6, y: 2 });
+        assert!(board.clear_completed().is_empty());
+    }
+
+    #[test]
+    fn rotate() {
+        // ....
+        // ....
+        // ..a.
+        // b.a.
+        let mut board = Board {
+            blocks: vec![
+                Block::new_settled("a", 2, 2),
+                Block::new_settled("b", 0, 3),
+                Block::new_settled("a", 2, 3),
+                Block::new_interactable("word", 0, 0),
+            ],
+            width: 4,
+            height: 4,
+        };
+        assert_unchanged(&board, |b| b.rotate());
+        for ch in "word".chars() {
+            board.get_focused_mut().unwrap().add_char(ch);
+        }
+        // Kicked right, clear of "b".
+        assert!(board.rotate());
+        assert_eq!(board.blocks[3].orientation, Orientation::Vertical);
+        assert_eq!(board.blocks[3].position, BoardPosition { x: 1, y: 0 });
+        assert_eq!(board.find_max_y(3), 0);
+        assert!(!board.left());
+        assert!(!board.right());
+
+        // Kicked back in from the wall.
+        board.blocks[3].position.x = 3;
+        assert!(board.rotate());
+        assert_eq!(board.blocks[3].orientation, Orientation::Horizontal);
+        assert_eq!(board.blocks[3].position, BoardPosition { x: 0, y: 0 });
+
+        // Every column is blocked somewhere.
+        board.blocks.push(Block::new_settled("x", 1, 3));
+        board.blocks.push(Block::new_settled("x", 3, 3));
+        board.sort();
+        assert_unchanged(&board, |b| b.rotate());
+    }
+
+    #[test]
+    fn bomb() {
+        let mut board = Board {
+            blocks: vec![
+                Block::new_settled("abcd", 0, 5),
+                Block::new_settled("ab", 0, 4),
+                Block::new_settled("ab", 0, 3),
+                Block::new_settled("ab", 0, 2),
+                Block::new_falling("xy", 2, 2).with_kind(Kind::Bomb),
+            ],
+            width: 4,
+            height: 6,
+        };
+        assert_eq!(board.fall_tick(false), Some(Msg::Updated));
+        assert_eq!(board.fall_tick(false), Some(Msg::Updated));
+        assert!(matches!(
+            board.fall_tick(false),
+            Some(Msg::BlocksSettled(_))
+        ));
+        // The bomb lands on row 4 and takes rows 3 to 5 with it.
+        let cleared = board.clear_completed();
+        assert_eq!(cleared.len(), 4);
+        assert_eq!(board.blocks, vec![Block::new_falling("ab", 0, 2)]);
+    }
+
+    #[test]
+    fn locked() {
+        let mut board = Board {
+            blocks: vec![
+                Block::new_settled("ab", 0, 3),
+                Block::new_falling("cd", 2, 2).with_kind(Kind::Locking),
+            ],
+            width: 4,
+            height: 4,
+        };
+        assert_eq!(board.fall_tick(false), Some(Msg::Updated));
+        assert!(matches!(
+            board.fall_tick(false),
+            Some(Msg::BlocksSettled(_))
+        ));
+        assert_eq!(board.blocks[1].kind, Kind::Locked);
+
+        // The row is full, but the lock holds it until "cd" is typed again.
+        assert!(board.clear_completed().is_empty());
+        assert!(board.get_focused().is_some_and(|b| b.is_unlocking()));
+        assert!(board.blocks[1].is_settled());
+        assert!(!board.right());
+        assert_eq!(board.fall_tick(true), None);
+        assert!(board.remove_focused().is_none());
+
+        // Giving up on it leaves it locked.
+        assert!(board.focus_next());
+        assert!(board.get_focused().is_none());
+        assert!(board.clear_completed().is_empty());
+
+        for ch in "cd".chars() {
+            board.get_focused_mut().unwrap().add_char(ch);
+        }
+        assert!(board.focus_next());
+        assert_eq!(board.clear_completed().len(), 2);
+        assert!(board.blocks.is_empty());
+    }
+
+    #[test]
+    fn drop_onto_unlocking() {
+        let mut board = Board {
+            blocks: vec![
+                Block::new_settled("ab", 0, 3),
+                Block::new_settled("cd", 2, 3).with_kind(Kind::Locked),
+            ],
+            width: 4,
+            height: 4,
+        };
+        assert!(board.clear_completed().is_empty());
+        assert!(board.blocks[1].is_unlocking());
+
+        // The word in play falls onto the locked block rather than into it, and typing still
+        // goes to the locked block.
+        board.push_block(Block::new_interactable("ef", 2, 0));
+        assert!(board.get_focused().is_some_and(|b| b.is_unlocking()));
+        while !matches!(board.fall_tick(true), Some(Msg::BlocksSettled(_))) {}
+        let landed = board.blocks.iter().find(|b| b.assigned_text() == "ef");
+        assert_eq!(landed.unwrap().position, BoardPosition { x: 2, y: 2 });
+        for (i, a) in board.blocks.iter().enumerate() {
+            assert!(board.blocks[i + 1..].iter().all(|b| !a.overlaps(b)));
+        }
+        assert!(
+            board
+                .get_focused()
+                .is_some_and(|b| b.assigned_text() == "cd")
+        );
+    }
+
+    #[test]
+    fn open_gaps() {
+        assert_eq!(
+            Board::new(8, 4).open_gaps(),
+            [Gap {
+                x: 0,
+                y: 3,
+                width: 8
+            }]
+        );
+
+        let mut board = Board::new(8, 4);
+        board.blocks = vec![
+            block(0, 0, "xyz"),
+            Block::new_settled("g", 7, 0),
+            Block::new_settled("f", 5, 2),
+            Block::new_settled("abc", 0, 3),
+            Block::new_settled("de", 5, 3),
+        ];
+        let gaps = board.open_gaps();
+        assert_eq!(
+            gaps,
+            [
+                Gap {
+                    x: 0,
+                    y: 2,
+                    width: 3
+                },
+                Gap {
+                    x: 3,
+                    y: 3,
+                    width: 2
+                },
+                Gap {
+                    x: 5,
+                    y: 1,
+                    width: 1
+                },
+                Gap {
+                    x: 6,
+                    y: 2,
+                    width: 1
+                },
+            ]
+        );
+        assert!(WordSizing::FillGaps.suits(3, &gaps));
+        assert!(!WordSizing::FillGaps.suits(4, &gaps));
+        assert!(WordSizing::AvoidGaps.suits(4, &gaps));
+        assert!(WordSizing::Any.suits(2, &gaps));
+    }
+
+    #[test]
+    fn analysis() {
+        let mut board = Board::new(6, 5);
+        board.blocks = vec![
+            block(0, 0, "xyz"),
+            Block::new_settled("hijk", 2, 2),
+            Block::new_settled("fg", 1, 3),
+            Block::new_settled("abc", 0, 4),
+            Block::new_settled("e", 4, 4),
+        ];
+        assert_eq!(board.column_heights(), [1, 2, 3, 3, 3, 3]);
+        assert_eq!(board.bumpiness(), 2);
+        let at = |x, y| BoardPosition { x, y };
+        assert_eq!(
+            board.holes(),
+            [at(3, 3), at(4, 3), at(5, 3), at(3, 4), at(5, 4)]
+        );
+        assert_eq!(board.filled_per_row(), [0, 0, 4, 2, 4]);
+        let gap = |x, y, width| Gap { x, y, width };
+        assert_eq!(board.row_gaps(3), [gap(0, 3, 1), gap(3, 3, 3)]);
+        assert_eq!(board.row_gaps(4), [gap(3, 4, 1), gap(5, 4, 1)]);
+        assert_eq!(board.row_gaps(0), [gap(0, 0, 6)]);
+        assert_eq!(board.row_gaps(5), []);
+        assert_eq!(
+            board.open_gaps(),
+            [gap(0, 3, 1), gap(1, 2, 1), gap(2, 1, 4)]
+        );
+        assert!(board.can_complete_row(2));
+        assert!(!board.can_complete_row(3));
+        assert!(!board.can_complete_row(4));
+        assert!(!board.can_complete_row(5));
+
+        // Holes under an overhang can still be filled by sliding a block in from the side.
+        let mut board = Board::new(3, 3);
+        board.blocks = vec![Block::new_settled("ab", 1, 1)];
+        assert_eq!(board.holes(), [at(1, 2), at(2, 2)]);
+        assert!(board.can_complete_row(2));
+        assert_eq!(Board::new(3, 3).bumpiness(), 0);
+    }
 }
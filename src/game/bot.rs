@@ -0,0 +1,197 @@
+use std::cmp::Ordering;
+
+use super::{
+    Event, Game,
+    block::{Block, Kind},
+    board::Board,
+};
+
+/// A computer opponent. It plays by producing the same events a player's keyboard would, one
+/// action every `action_interval`, so it can be plugged into anything that takes [`Event`]s.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bot {
+    action_interval: f64,
+    timer: f64,
+}
+
+impl Default for Bot {
+    fn default() -> Self {
+        Self::new(250.0)
+    }
+}
+
+impl Bot {
+    /// `action_interval` is in the same unit as [`Event::Tick`].
+    #[inline]
+    pub fn new(action_interval: f64) -> Self {
+        Self {
+            action_interval,
+            timer: action_interval,
+        }
+    }
+
+    /// Advance the bot's clock by `delta_time` and return the events it wants to send, in order.
+    pub fn think(&mut self, game: &Game, delta_time: f64) -> Vec<Event> {
+        self.timer -= delta_time;
+        let mut game = game.clone();
+        let mut events = Vec::new();
+        while self.timer <= 0.0 {
+            let Some(event) = Self::next_event(&game) else {
+                break;
+            };
+            let event = if game.handle_event(event) {
+                event
+            } else if matches!(event, Event::Left | Event::Right) && game.handle_event(Event::Next)
+            {
+                // Something is in the way, so drop it where it is.
+                Event::Next
+            } else {
+                break;
+            };
+            events.push(event);
+            self.timer += self.action_interval;
+        }
+        // Don't save up actions while there's nothing to do.
+        self.timer = self.timer.max(0.0);
+        events
+    }
+
+    fn next_event(game: &Game) -> Option<Event> {
+        if !game.is_playing() {
+            return None;
+        }
+        let focus = game.board().get_focused()?;
+        let typed = focus.input_text();
+        if typed
+            .chars()
+            .enumerate()
+            .any(|(i, ch)| !focus.matches(i, ch))
+        {
+            return Some(Event::Delete);
+        }
+        if let Some(ch) = focus.assigned_text()[typed.len()..].chars().next() {
+            // Any letter will do for a wildcard.
+            let ch = if focus.kind() == Kind::Wildcard {
+                'x'
+            } else {
+                ch
+            };
+            return Some(Event::Type(ch));
+        }
+        if !focus.is_movable() {
+            return Some(Event::Next);
+        }
+        Some(
+            match focus.position().x.cmp(&Self::target_x(game.board(), focus)) {
+                Ordering::Less => Event::Right,
+                Ordering::Greater => Event::Left,
+                Ordering::Equal => Event::Next,
+            },
+        )
+    }
+
+    /// Pick the column to drop `focus` at. Completing a row comes first, then landing as deep as
+    /// possible, then packing tightly against walls and neighbours, then staying close by. The
+    /// placement is judged by the bottom row of the piece, which is the one resting on the stack.
+    fn target_x(board: &Board, focus: &Block) -> u8 {
+        (0..=board.width() - focus.width())
+            .max_by_key(|&x| {
+                let mut candidate = focus.clone();
+                candidate.position.x = x;
+                candidate.position.y = board.landing_y(&candidate);
+                let bottom = candidate.segments().last().unwrap();
+                let (x, y, end_x) = (bottom.position.x, bottom.position.y, bottom.end_x());
+                let row = || {
+                    board
+                        .blocks()
+                        .iter()
+                        .filter(|b| b.is_settled())
+                        .flat_map(|b| b.segments())
+                        .filter(move |s| s.position.y == y)
+                };
+                let filled = row().map(|s| s.len as usize).sum::<usize>() + bottom.len as usize;
+                let touches_left = x == 0 || row().any(|s| s.end_x() == x);
+                let touches_right = end_x == board.width() || row().any(|s| s.position.x == end_x);
+                (
+                    filled == board.width() as usize,
+                    y,
+                    touches_left as u8 + touches_right as u8,
+                    filled,
+                    u8::MAX - candidate.position.x.abs_diff(focus.position().x),
+                )
+            })
+            .unwrap_or(focus.position().x)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::settings::Settings;
+
+    #[test]
+    fn types_then_moves_then_drops() {
+        let settings = Settings::default()
+            .with_starts_with_one(false)
+            .with_width(8)
+            .with_height(8);
+        let mut game = Game::new(settings);
+        game.board.push_block(Block::new_settled("abcd", 0, 7));
+        game.board.push_block(Block::new_interactable("me", 1, 0));
+
+        let mut bot = Bot::new(1.0);
+        assert!(bot.think(&game, 0.5).is_empty());
+        let events = bot.think(&game, 5.5);
+        assert!(matches!(
+            events.as_slice(),
+            [
+                Event::Type('m'),
+                Event::Type('e'),
+                Event::Right,
+                Event::Right,
+                Event::Right,
+                Event::Next
+            ]
+        ));
+        for event in events {
+            assert!(game.handle_event(event));
+        }
+        assert!(game.board.get_focused().is_none());
+        assert!(bot.think(&game, 1.0).is_empty());
+    }
+
+    #[test]
+    fn fixes_typos() {
+        let mut game = Game::new(Settings::default().with_starts_with_one(false));
+        game.board.push_block(Block::new_interactable("me", 0, 0));
+        game.handle_event(Event::Type('x'));
+        let mut bot = Bot::new(1.0);
+        assert!(matches!(
+            bot.think(&game, 3.0).as_slice(),
+            [Event::Delete, Event::Type('m'), Event::Type('e')]
+        ));
+    }
+
+    #[test]
+    fn plays_a_whole_game() {
+        let settings = Settings::default()
+            .with_width(10)
+            .with_height(10)
+            .with_spawn_interval(20.0)
+            .with_fall_interval(1.0)
+            .with_drift_interval(4);
+        let mut game = Game::new(settings);
+        let mut bot = Bot::new(1.0);
+        for _ in 0..10_000 {
+            // The bot only sends events that it has already checked do something.
+            for event in bot.think(&game, 1.0) {
+                assert!(game.handle_event(event));
+            }
+            game.handle_event(Event::Tick(1.0));
+            if game.is_game_over() {
+                return;
+            }
+        }
+        panic!("blocks spawn faster than they can be cleared, so the game should end eventually");
+    }
+}
@@ -0,0 +1,120 @@
+//! Daily challenge. Everyone playing on the same day gets the same seed, and so the same words in
+//! the same places, which makes scores comparable.
+
+use std::fmt::{self, Display};
+use std::hash::Hasher;
+
+use super::{Fnv1a, Game, settings::Settings};
+
+/// A calendar date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Day {
+    pub year: i32,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl Display for Day {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl Day {
+    #[inline]
+    pub fn new(year: i32, month: u8, day: u8) -> Self {
+        Self { year, month, day }
+    }
+
+    /// The seed of this day's challenge.
+    pub fn seed(self) -> u64 {
+        let mut hasher = Fnv1a::default();
+        hasher.write(format!("typetris daily {self}").as_bytes());
+        hasher.finish()
+    }
+
+    /// Settings for this day's challenge.
+    #[inline]
+    pub fn settings(self) -> Settings {
+        Settings::default().with_seed(Some(self.seed()))
+    }
+
+    /// A short summary of `game` for pasting into chat: the score, rows cleared, typing speed and
+    /// a picture of the stack.
+    pub fn result_card(self, game: &Game) -> String {
+        let stats = game.stats();
+        let mut card = format!(
+            "Typetris daily {self}\nScore {} · {} rows · {:.0} WPM\n",
+            game.score(),
+            stats.rows(),
+            stats.wpm(),
+        );
+
+        let board = game.board();
+        let mut grid = vec![vec!['⬛'; board.width() as usize]; board.height() as usize];
+        for block in board.blocks().iter().filter(|b| b.is_settled()) {
+            let cell = if block.is_garbage() { '🟫' } else { '🟪' };
+            for segment in block.segments() {
+                for x in segment.position.x..segment.end_x() {
+                    grid[segment.position.y as usize][x as usize] = cell;
+                }
+            }
+        }
+        // Only show the stack, not the empty sky above it.
+        for row in grid.iter().skip_while(|row| row.iter().all(|&c| c == '⬛')) {
+            row.iter().for_each(|&c| card.push(c));
+            card.push('\n');
+        }
+        card.pop();
+        card
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::{Event, block::Block};
+
+    #[test]
+    fn same_day_same_game() {
+        let today = Day::new(2026, 10, 18);
+        assert_eq!(today.to_string(), "2026-10-18");
+        assert_eq!(today.seed(), Day::new(2026, 10, 18).seed());
+        assert_ne!(today.seed(), Day::new(2026, 10, 19).seed());
+        assert_ne!(today.seed(), Day::new(2025, 10, 18).seed());
+
+        let mut a = Game::new(today.settings().with_spawn_interval(1.0));
+        let mut b = Game::new(today.settings().with_spawn_interval(1.0));
+        for _ in 0..10 {
+            a.handle_event(Event::Tick(1.0));
+            b.handle_event(Event::Tick(1.0));
+        }
+        assert_eq!(a.board(), b.board());
+    }
+
+    #[test]
+    fn result_card() {
+        let settings = Settings::default()
+            .with_starts_with_one(false)
+            .with_width(4)
+            .with_height(4)
+            .with_spawn_interval(60_000.0)
+            .with_fall_interval(10_000.0);
+        let mut game = Game::new(settings);
+        game.board.push_block(Block::new_settled("ab", 1, 3));
+        game.board.push_block(Block::garbage(3, 3));
+        game.board.push_block(Block::new_settled("c", 2, 2));
+        game.board.push_block(Block::new_interactable("word", 0, 0));
+        for ch in "word".chars() {
+            game.handle_event(Event::Type(ch));
+        }
+        for _ in 0..12 {
+            game.handle_event(Event::Tick(1_000.0));
+        }
+
+        assert_eq!(
+            Day::new(2026, 1, 2).result_card(&game),
+            "Typetris daily 2026-01-02\nScore 0 · 0 rows · 4 WPM\n⬛⬛🟪⬛\n⬛🟪🟪🟫"
+        );
+    }
+}
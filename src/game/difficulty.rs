@@ -0,0 +1,130 @@
+use std::ops::RangeInclusive;
+
+use super::layout::Layout;
+
+/// How often each letter comes up in English text, in percent, from a to z.
+const LETTER_FREQUENCIES: [f64; 26] = [
+    8.2, 1.5, 2.8, 4.3, 12.7, 2.2, 2.0, 6.1, 7.0, 0.15, 0.77, 4.0, 2.4, 6.7, 7.5, 1.9, 0.095, 6.0,
+    6.3, 9.1, 2.8, 0.98, 2.4, 0.15, 2.0, 0.074,
+];
+/// Words this long or longer score the most for length.
+const LONG_WORD: usize = 10;
+/// How much length, rare letters, awkward bigrams and capitals add to a word's score. They add up
+/// to 1.
+const LENGTH_WEIGHT: f64 = 0.45;
+const RARITY_WEIGHT: f64 = 0.3;
+const BIGRAM_WEIGHT: f64 = 0.15;
+const CAPITAL_WEIGHT: f64 = 0.1;
+/// How wide a band of scores [`WordDifficulty::ByLevel`] picks from.
+const BAND_WIDTH: f64 = 0.15;
+/// How much harder [`WordDifficulty::ByLevel`]'s band gets every level.
+const LEVEL_STEP: f64 = 0.025;
+/// Where [`WordDifficulty::ByLevel`]'s band stops getting harder. The built-in words score up to
+/// about 0.4, so the last band has the hardest of them.
+const HARDEST_BAND: f64 = 0.27;
+
+/// How rare `ch` is, from 0 for e to 1 for z. Anything that's not a letter counts as rare.
+fn rarity(ch: char) -> f64 {
+    if !ch.is_ascii_alphabetic() {
+        return 1.0;
+    }
+    let frequency = LETTER_FREQUENCIES[(ch.to_ascii_lowercase() as u8 - b'a') as usize];
+    let most = LETTER_FREQUENCIES[(b'e' - b'a') as usize];
+    let least = LETTER_FREQUENCIES[(b'z' - b'a') as usize];
+    (most / frequency).ln() / (most / least).ln()
+}
+
+/// Whether `a` then `b` are typed with the same finger on different keys, which is slow.
+fn is_awkward(a: char, b: char) -> bool {
+    let (a, b) = (a.to_ascii_lowercase(), b.to_ascii_lowercase());
+    a != b
+        && matches!(
+            (Layout::Qwerty.key(a), Layout::Qwerty.key(b)),
+            (Some((_, f)), Some((_, g))) if f == g
+        )
+}
+
+/// How hard `word` is to type, from 0 for the easiest to 1 for the hardest. Long words, rare
+/// letters, bigrams typed with one finger on QWERTY and capitals all make words harder.
+pub fn difficulty(word: &str) -> f64 {
+    let len = word.chars().count();
+    if len == 0 {
+        return 0.0;
+    }
+    let length = (len - 1) as f64 / (LONG_WORD - 1) as f64;
+    let rarity = word.chars().map(rarity).sum::<f64>() / len as f64;
+    let chars: Vec<char> = word.chars().collect();
+    let awkward = chars.windows(2).filter(|w| is_awkward(w[0], w[1])).count();
+    let bigrams = if len > 1 {
+        awkward as f64 / (len - 1) as f64
+    } else {
+        0.0
+    };
+    let capitals = if word.chars().any(|c| c.is_ascii_uppercase()) {
+        1.0
+    } else {
+        0.0
+    };
+    (LENGTH_WEIGHT * length.min(1.0)
+        + RARITY_WEIGHT * rarity
+        + BIGRAM_WEIGHT * bigrams
+        + CAPITAL_WEIGHT * capitals)
+        .clamp(0.0, 1.0)
+}
+
+/// Which words spawn, by their [`difficulty`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum WordDifficulty {
+    /// Any word, however easy or hard.
+    #[default]
+    Any,
+    /// Words scoring from `min` to `max`.
+    Band { min: f64, max: f64 },
+    /// The easiest words on the first level, and a little harder ones every level after.
+    ByLevel,
+}
+
+impl WordDifficulty {
+    /// The scores of words to spawn on `level`, counting from 1. If no word that fits the board
+    /// scores in the band, any word that fits does.
+    pub fn band(self, level: usize) -> RangeInclusive<f64> {
+        match self {
+            Self::Any => 0.0..=1.0,
+            Self::Band { min, max } => min..=max,
+            Self::ByLevel => {
+                let min = (level.saturating_sub(1) as f64 * LEVEL_STEP).min(HARDEST_BAND);
+                min..=min + BAND_WIDTH
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::block::WORDS;
+
+    #[test]
+    fn t0() {
+        assert!(difficulty("the") < difficulty("interest"));
+        assert!(difficulty("at") < difficulty("zq"));
+        assert!(difficulty("ted") < difficulty("Ted"));
+        // e and d are both typed with the left middle finger.
+        assert!(difficulty("ade") > difficulty("ate"));
+        assert_eq!(difficulty(""), 0.0);
+        for word in WORDS {
+            assert!((0.0..=1.0).contains(&difficulty(word)), "{word}");
+        }
+
+        assert_eq!(WordDifficulty::Any.band(100), 0.0..=1.0);
+        assert!(WordDifficulty::ByLevel.band(1).contains(&0.0));
+        let hardest = WORDS.iter().map(|w| difficulty(w)).fold(0.0, f64::max);
+        assert!(WordDifficulty::ByLevel.band(1000).contains(&hardest));
+        // Every level has some words to pick from.
+        for level in 1..30 {
+            let band = WordDifficulty::ByLevel.band(level);
+            let words = WORDS.iter().filter(|w| band.contains(&difficulty(w)));
+            assert!(words.count() >= 10, "level {level}");
+        }
+    }
+}
@@ -1,4 +1,4 @@
-const WORDS: &[&str] = &[
+pub(super) const WORDS: &[&str] = &[
     "the", "be", "of", "and", "a", "to", "in", "he", "have", "it", "that", "for", "they", "I",
     "with", "as", "not", "on", "she", "at", "by", "this", "we", "you", "do", "but", "from", "or",
     "which", "one", "would", "all", "will", "there", "say", "who", "make", "when", "can", "more",
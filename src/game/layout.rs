@@ -0,0 +1,181 @@
+use serde::{Deserialize, Serialize};
+
+/// A row of letter keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Row {
+    Top,
+    Home,
+    Bottom,
+}
+
+impl Row {
+    pub const ALL: [Self; 3] = [Self::Top, Self::Home, Self::Bottom];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Top => "Top row",
+            Self::Home => "Home row",
+            Self::Bottom => "Bottom row",
+        }
+    }
+}
+
+/// The finger a key is pressed with when touch typing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Finger {
+    LeftPinky,
+    LeftRing,
+    LeftMiddle,
+    LeftIndex,
+    RightIndex,
+    RightMiddle,
+    RightRing,
+    RightPinky,
+}
+
+impl Finger {
+    pub const ALL: [Self; 8] = [
+        Self::LeftPinky,
+        Self::LeftRing,
+        Self::LeftMiddle,
+        Self::LeftIndex,
+        Self::RightIndex,
+        Self::RightMiddle,
+        Self::RightRing,
+        Self::RightPinky,
+    ];
+
+    /// The finger that presses the key in `column` of a row, counting from the left. Index fingers
+    /// take two columns each, the one in front of them and the one towards the middle.
+    fn of_column(column: usize) -> Self {
+        match column {
+            0 => Self::LeftPinky,
+            1 => Self::LeftRing,
+            2 => Self::LeftMiddle,
+            3 | 4 => Self::LeftIndex,
+            5 | 6 => Self::RightIndex,
+            7 => Self::RightMiddle,
+            8 => Self::RightRing,
+            _ => Self::RightPinky,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::LeftPinky => "Left pinky",
+            Self::LeftRing => "Left ring",
+            Self::LeftMiddle => "Left middle",
+            Self::LeftIndex => "Left index",
+            Self::RightIndex => "Right index",
+            Self::RightMiddle => "Right middle",
+            Self::RightRing => "Right ring",
+            Self::RightPinky => "Right pinky",
+        }
+    }
+}
+
+/// Which character is on which key.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize, Deserialize,
+)]
+pub enum Layout {
+    #[default]
+    Qwerty,
+    Dvorak,
+    Colemak,
+    Azerty,
+}
+
+impl Layout {
+    pub const ALL: [Self; 4] = [Self::Qwerty, Self::Dvorak, Self::Colemak, Self::Azerty];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Qwerty => "QWERTY",
+            Self::Dvorak => "Dvorak",
+            Self::Colemak => "Colemak",
+            Self::Azerty => "AZERTY",
+        }
+    }
+
+    /// The ten keys of `row` a touch typist's fingers rest over or reach to, from left to right.
+    pub fn row(self, row: Row) -> &'static str {
+        match (self, row) {
+            (Self::Qwerty, Row::Top) => "qwertyuiop",
+            (Self::Qwerty, Row::Home) => "asdfghjkl;",
+            (Self::Qwerty, Row::Bottom) => "zxcvbnm,./",
+            (Self::Dvorak, Row::Top) => "',.pyfgcrl",
+            (Self::Dvorak, Row::Home) => "aoeuidhtns",
+            (Self::Dvorak, Row::Bottom) => ";qjkxbmwvz",
+            (Self::Colemak, Row::Top) => "qwfpgjluy;",
+            (Self::Colemak, Row::Home) => "arstdhneio",
+            (Self::Colemak, Row::Bottom) => "zxcvbkm,./",
+            (Self::Azerty, Row::Top) => "azertyuiop",
+            (Self::Azerty, Row::Home) => "qsdfghjklm",
+            (Self::Azerty, Row::Bottom) => "wxcvbn,;:!",
+        }
+    }
+
+    /// The row and finger `ch` is typed with, ignoring case, or `None` if it's not on any of the
+    /// three letter rows.
+    pub fn key(self, ch: char) -> Option<(Row, Finger)> {
+        let ch = ch.to_ascii_lowercase();
+        Row::ALL.into_iter().find_map(|row| {
+            let column = self.row(row).chars().position(|c| c == ch)?;
+            Some((row, Finger::of_column(column)))
+        })
+    }
+
+    /// Whether every character of `word` can be typed without leaving `rows`.
+    pub fn only_on(self, word: &str, rows: &[Row]) -> bool {
+        word.chars()
+            .all(|ch| self.key(ch).is_some_and(|(row, _)| rows.contains(&row)))
+    }
+
+    /// The letters that move the focused block left, turn it and move it right when typed with
+    /// Ctrl held, after Vim's h, k and l. Colemak has its own well known set on the home row.
+    /// Dvorak keeps Vim's letters, since the keys in their place are Ctrl+T and Ctrl+N, which
+    /// browsers keep for themselves.
+    pub fn shortcuts(self) -> [char; 3] {
+        match self {
+            Self::Qwerty | Self::Dvorak | Self::Azerty => ['h', 'k', 'l'],
+            Self::Colemak => ['h', 'e', 'i'],
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn t0() {
+        for layout in Layout::ALL {
+            for row in Row::ALL {
+                assert_eq!(layout.row(row).chars().count(), 10);
+            }
+        }
+        assert_eq!(
+            Layout::Qwerty.key('F'),
+            Some((Row::Home, Finger::LeftIndex))
+        );
+        assert_eq!(
+            Layout::Colemak.key('n'),
+            Some((Row::Home, Finger::RightIndex))
+        );
+        assert_eq!(
+            Layout::Dvorak.key('z'),
+            Some((Row::Bottom, Finger::RightPinky))
+        );
+        assert_eq!(Layout::Azerty.key('a'), Some((Row::Top, Finger::LeftPinky)));
+        assert_eq!(Layout::Qwerty.key('1'), None);
+
+        assert!(Layout::Qwerty.only_on("flask", &[Row::Home]));
+        assert!(!Layout::Qwerty.only_on("flake", &[Row::Home]));
+        assert!(Layout::Colemak.only_on("STRANDS", &[Row::Home]));
+
+        for ch in Layout::Colemak.shortcuts() {
+            assert_eq!(Layout::Colemak.key(ch).unwrap().0, Row::Home);
+        }
+    }
+}
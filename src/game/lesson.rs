@@ -0,0 +1,164 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::Game;
+use super::layout::{Layout, Row};
+use super::settings::Settings;
+use super::stats::Stats;
+use super::words::WordSource;
+
+/// One step of the lesson curriculum: a game of words made of the letters on some rows of the
+/// keyboard, that's passed by typing them accurately and fast enough.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stage {
+    pub name: &'static str,
+    pub rows: &'static [Row],
+    /// Words to type and drop to finish the stage.
+    pub words: usize,
+    /// Least fraction of correct keystrokes to pass.
+    pub min_accuracy: f64,
+    pub min_wpm: f64,
+}
+
+pub const STAGES: [Stage; 5] = [
+    Stage {
+        name: "Home row",
+        rows: &[Row::Home],
+        words: 15,
+        min_accuracy: 0.9,
+        min_wpm: 10.0,
+    },
+    Stage {
+        name: "Home and top rows",
+        rows: &[Row::Home, Row::Top],
+        words: 20,
+        min_accuracy: 0.9,
+        min_wpm: 15.0,
+    },
+    Stage {
+        name: "Home and bottom rows",
+        rows: &[Row::Home, Row::Bottom],
+        words: 20,
+        min_accuracy: 0.9,
+        min_wpm: 15.0,
+    },
+    Stage {
+        name: "All rows",
+        rows: &Row::ALL,
+        words: 25,
+        min_accuracy: 0.92,
+        min_wpm: 20.0,
+    },
+    Stage {
+        name: "All rows, faster",
+        rows: &Row::ALL,
+        words: 30,
+        min_accuracy: 0.95,
+        min_wpm: 30.0,
+    },
+];
+
+impl Stage {
+    /// The letters on this stage's rows of `layout`.
+    pub fn letters(&self, layout: Layout) -> String {
+        self.rows
+            .iter()
+            .flat_map(|&row| layout.row(row).chars())
+            .filter(char::is_ascii_alphabetic)
+            .collect()
+    }
+
+    pub fn settings(&self) -> Settings {
+        Settings::default().with_word_goal(Some(self.words))
+    }
+
+    /// A game of this stage for someone typing on `layout`.
+    pub fn game(&self, layout: Layout) -> Game {
+        Game::with_words(self.settings(), WordSource::Letters(self.letters(layout)))
+    }
+
+    /// Whether a finished game with `stats` passes this stage.
+    pub fn passed(&self, stats: &Stats) -> bool {
+        stats.accuracy() >= self.min_accuracy && stats.wpm() >= self.min_wpm
+    }
+}
+
+/// How many of the [`STAGES`] a player has passed on each layout. Stages have to be passed in
+/// order.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Progress {
+    passed: BTreeMap<Layout, usize>,
+}
+
+impl Progress {
+    pub fn stages_passed(&self, layout: Layout) -> usize {
+        self.passed.get(&layout).copied().unwrap_or_default()
+    }
+
+    /// Whether `stage` can be played on `layout` yet: it's the first, or the one before it has been
+    /// passed.
+    pub fn is_unlocked(&self, layout: Layout, stage: usize) -> bool {
+        stage < STAGES.len() && stage <= self.stages_passed(layout)
+    }
+
+    /// The first stage not passed yet on `layout`, or the last if they all have been.
+    pub fn current_stage(&self, layout: Layout) -> usize {
+        self.stages_passed(layout).min(STAGES.len() - 1)
+    }
+
+    /// Record passing `stage` on `layout`, which unlocks the next one.
+    pub fn record_pass(&mut self, layout: Layout, stage: usize) {
+        let passed = self.passed.entry(layout).or_default();
+        *passed = (*passed).max(stage + 1).min(STAGES.len());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::Event;
+
+    #[test]
+    fn t0() {
+        assert_eq!(STAGES[0].letters(Layout::Qwerty), "asdfghjkl");
+        assert_eq!(STAGES[0].letters(Layout::Colemak), "arstdhneio");
+        assert_eq!(STAGES[3].letters(Layout::Dvorak).len(), 26);
+
+        let stage = STAGES[0];
+        let mut game = stage.game(Layout::Qwerty);
+        while !game.is_finished() {
+            assert!(!game.is_game_over());
+            if let Some(focus) = game.board().get_focused() {
+                let text = focus.assigned_text().to_string();
+                assert!(text.chars().all(|c| "asdfghjkl".contains(c)), "{text}");
+                for ch in text.chars() {
+                    game.handle_event(Event::Type(ch));
+                }
+                game.handle_event(Event::Next);
+            }
+            game.handle_event(Event::Tick(1_000.0));
+        }
+        assert_eq!(game.stats().words(), stage.words);
+        assert!(game.take_notices().contains(&crate::game::Notice::Finished));
+        assert!(!game.handle_event(Event::Type('a')));
+        assert_eq!(
+            stage.passed(&game.stats()),
+            game.stats().wpm() >= stage.min_wpm
+        );
+
+        let mut progress = Progress::default();
+        assert_eq!(progress.current_stage(Layout::Colemak), 0);
+        assert!(!progress.is_unlocked(Layout::Colemak, 1));
+        progress.record_pass(Layout::Colemak, 0);
+        assert!(progress.is_unlocked(Layout::Colemak, 1));
+        assert!(!progress.is_unlocked(Layout::Qwerty, 1));
+        for stage in 0..STAGES.len() {
+            progress.record_pass(Layout::Colemak, stage);
+        }
+        assert_eq!(progress.current_stage(Layout::Colemak), STAGES.len() - 1);
+
+        let json = serde_json::to_string(&progress).unwrap();
+        assert_eq!(serde_json::from_str::<Progress>(&json).unwrap(), progress);
+    }
+}
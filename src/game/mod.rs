@@ -1,24 +1,89 @@
+mod auto_shift;
 pub mod block;
 pub mod board;
+pub mod bot;
+pub mod daily;
+pub mod difficulty;
+pub mod layout;
+pub mod lesson;
+pub mod online;
+pub mod passage;
+pub mod power_up;
+pub mod practice;
+pub mod quotes;
 pub mod settings;
+pub mod stats;
 mod timer;
+pub mod versus;
+pub mod words;
 
 use std::collections::BTreeSet;
+use std::hash::{Hash, Hasher};
 
+use auto_shift::{AutoShift, Direction};
 use block::Block;
 use board::Board;
+use difficulty::difficulty;
 use getset::{CopyGetters, Getters, WithSetters};
+use power_up::{PowerUp, PowerUps};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use serde::{Deserialize, Serialize};
 use settings::Settings;
+use stats::Stats;
 use timer::Timer;
+use words::{RecentWords, WordSource};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Mixed into [`Settings::seed`] to seed the power-up random number generator.
+const POWER_UP_SALT: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// Rows to clear to go up a level.
+pub const ROWS_PER_LEVEL: usize = 10;
+
+/// Shortest step the game is simulated in, however short [`Settings::time_step`] and the fall and
+/// spawn intervals are, so a tick always ends.
+const MIN_TIME_STEP: f64 = 0.01;
+/// Most steps a single tick simulates. Time left over after that is dropped, so that very short
+/// steps can't hold up the page for long.
+const MAX_STEPS_PER_TICK: usize = 1_000;
+
+/// FNV-1a. Unlike the standard library's hasher, its output is stable across builds and
+/// platforms, so hashes can be compared between clients.
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv1a {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    // Lengths and counts are hashed as `usize`, whose width differs between wasm and native.
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub(crate) enum State {
     Splash,
     Playing,
     GameOver,
+    /// Reached [`Settings::word_goal`], or typed every word of its [`WordSource::Text`].
+    Finished,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Event {
     Tick(f64),
     Type(char),
@@ -26,9 +91,48 @@ pub enum Event {
     Next,
     Left,
     Right,
+    /// Start holding the left key: move left now, and keep moving left after
+    /// [`Settings::auto_shift_delay`] until it's released.
+    LeftPressed,
+    LeftReleased,
+    /// Start holding the right key: move right now, and keep moving right after
+    /// [`Settings::auto_shift_delay`] until it's released.
+    RightPressed,
+    RightReleased,
+    /// Turn the focused block between horizontal and vertical.
+    Rotate,
+    /// Use the power-up in the given inventory slot. The slots' keys are digits, so if the focused
+    /// word calls for that digit next, it's typed instead.
+    UsePowerUp(usize),
     NewGame,
 }
 
+/// Something that happened in a game that's worth showing or playing a sound for, as opposed to
+/// an [`Event`] that makes things happen. They queue up until taken with [`Game::take_notices`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Notice {
+    /// A character was typed into the focused block.
+    Typed {
+        correct: bool,
+    },
+    /// The focused block was dropped.
+    Dropped,
+    /// A block landed, as it was before being split into rows.
+    BlockSettled(Block),
+    /// Rows were cleared, with the blocks that were in them, where they were.
+    RowsCleared {
+        rows: Vec<u8>,
+        blocks: Vec<Block>,
+    },
+    BlockSpawned,
+    /// The game went up to the given level.
+    LevelUp(usize),
+    GameOver,
+    /// The game reached its [`Settings::word_goal`], or every word of its [`WordSource::Text`]
+    /// was typed.
+    Finished,
+}
+
 #[derive(Debug, Clone, PartialEq, CopyGetters, Getters, WithSetters)]
 pub struct Game {
     #[getset(get = "pub", set_with)]
@@ -38,8 +142,32 @@ pub struct Game {
     #[getset(get = "pub")]
     board: Board,
     timer: Timer,
+    /// Time that has passed but hasn't been simulated yet because it's less than a step.
+    lag: f64,
+    auto_shift: AutoShift,
+    /// Picks words, shapes and where blocks spawn, so seeded games spawn the same blocks.
+    rng: StdRng,
+    /// Picks power-ups and shrunk words. How much it's used depends on how the player types, so
+    /// it's kept apart from [`Self::rng`].
+    power_up_rng: StdRng,
     #[getset(get_copy = "pub")]
     score: usize,
+    #[getset(get_copy = "pub")]
+    stats: Stats,
+    /// Garbage rows sent by the opponent that haven't risen into the board yet.
+    #[getset(get_copy = "pub")]
+    incoming_garbage: usize,
+    /// Garbage rows earned by clearing rows that haven't been sent to the opponent yet.
+    #[getset(get_copy = "pub")]
+    outgoing_garbage: usize,
+    #[getset(get = "pub")]
+    power_ups: PowerUps,
+    /// Where the words of new blocks come from.
+    #[getset(get = "pub")]
+    words: WordSource,
+    /// Words spawned lately, that shouldn't come up again yet.
+    recent_words: RecentWords,
+    notices: Vec<Notice>,
 }
 
 impl Default for Game {
@@ -50,7 +178,7 @@ impl Default for Game {
 
 impl Game {
     pub fn splash() -> Self {
-        let mut board = Board::new(12, 16, false);
+        let mut board = Board::new(12, 16);
         use block::State as S;
         board.push_block(Block::new("Typetris", S::Interactable, 2, 0));
         board.push_block(Block::new("It's", S::Interactable, 0, 4));
@@ -76,7 +204,18 @@ impl Game {
                 settings.spawn_interval,
                 settings.drift_interval,
             ),
+            lag: 0.0,
+            auto_shift: AutoShift::new(settings.auto_shift_delay, settings.auto_repeat_interval),
+            rng: Self::make_rng(&settings, 0),
+            power_up_rng: Self::make_rng(&settings, POWER_UP_SALT),
             score: 0,
+            stats: Stats::default(),
+            incoming_garbage: 0,
+            outgoing_garbage: 0,
+            power_ups: PowerUps::default(),
+            words: WordSource::default(),
+            recent_words: RecentWords::default(),
+            notices: Vec::new(),
             state: State::Splash,
             settings,
         }
@@ -84,23 +223,70 @@ impl Game {
 
     #[inline]
     pub fn new(settings: Settings) -> Self {
+        Self::with_words(settings, WordSource::default())
+    }
+
+    /// Like [`Self::new`], but with the words of blocks coming from `words`.
+    pub fn with_words(settings: Settings, words: WordSource) -> Self {
         if settings.starts_with_splash {
-            Self::splash().with_settings(settings.with_starts_with_splash(false))
-        } else {
             Self {
-                board: Board::new(settings.width, settings.height, settings.starts_with_one),
+                words,
+                ..Self::splash().with_settings(settings.with_starts_with_splash(false))
+            }
+        } else {
+            let mut ret = Self {
+                board: Board::new(settings.width, settings.height),
                 timer: Timer::new(
                     settings.fall_interval,
                     settings.spawn_interval,
                     settings.drift_interval,
                 ),
+                lag: 0.0,
+                auto_shift: AutoShift::new(
+                    settings.auto_shift_delay,
+                    settings.auto_repeat_interval,
+                ),
+                rng: Self::make_rng(&settings, 0),
+                power_up_rng: Self::make_rng(&settings, POWER_UP_SALT),
                 score: 0,
+                stats: Stats::default(),
+                incoming_garbage: 0,
+                outgoing_garbage: 0,
+                power_ups: PowerUps::default(),
+                words,
+                recent_words: RecentWords::new(settings.repeat_window),
+                notices: Vec::new(),
                 state: State::Playing,
                 settings,
+            };
+            if settings.starts_with_one {
+                ret.spawn();
             }
+            ret
         }
     }
 
+    /// A random number generator seeded from [`Settings::seed`] mixed with `salt`, or from the OS
+    /// if there's no seed.
+    #[inline]
+    fn make_rng(settings: &Settings, salt: u64) -> StdRng {
+        settings.seed.map_or_else(StdRng::from_os_rng, |seed| {
+            StdRng::seed_from_u64(seed ^ salt)
+        })
+    }
+
+    /// Hash of everything that matters about the state of the game. Two copies of a game that
+    /// have been fed the same events have the same hash; if they don't, they have drifted apart.
+    pub fn state_hash(&self) -> u64 {
+        let mut hasher = Fnv1a::default();
+        self.state.hash(&mut hasher);
+        self.board.hash(&mut hasher);
+        self.score.hash(&mut hasher);
+        self.incoming_garbage.hash(&mut hasher);
+        self.power_ups.inventory().hash(&mut hasher);
+        hasher.finish()
+    }
+
     /// Handle the given event and return a boolean indicating whether state has changed.
     #[inline]
     pub fn handle_event(&mut self, event: Event) -> bool {
@@ -115,55 +301,284 @@ impl Game {
             (true, Event::Next) => self.focus_next(),
             (true, Event::Left) => self.left(),
             (true, Event::Right) => self.right(),
+            (true, Event::LeftPressed) => {
+                self.auto_shift.press(Direction::Left);
+                self.left()
+            }
+            (true, Event::RightPressed) => {
+                self.auto_shift.press(Direction::Right);
+                self.right()
+            }
+            (_, Event::LeftReleased) => {
+                self.auto_shift.release(Direction::Left);
+                false
+            }
+            (_, Event::RightReleased) => {
+                self.auto_shift.release(Direction::Right);
+                false
+            }
+            (true, Event::Rotate) => self.rotate(),
+            (true, Event::UsePowerUp(index)) => {
+                let digit = u32::try_from(index + 1)
+                    .ok()
+                    .and_then(|d| char::from_digit(d, 10));
+                match digit.filter(|&d| self.expects(d)) {
+                    Some(digit) => self.add_char(digit),
+                    None => self.use_power_up(index),
+                }
+            }
             (false, _) => false,
         }
     }
 
+    /// Advance the game by `delta_time`, in as many fixed steps as fit. Time that doesn't make up
+    /// a whole step is carried over to the next tick, and anything over
+    /// [`Settings::max_catch_up`] or [`MAX_STEPS_PER_TICK`] steps is dropped rather than played
+    /// out all at once.
     fn tick(&mut self, delta_time: f64) -> bool {
+        let delta_time = delta_time.min(self.settings.max_catch_up);
+        self.stats.record_time(delta_time);
+        self.lag += delta_time;
+        let step = self
+            .settings
+            .time_step
+            .min(self.settings.fall_interval)
+            .min(self.settings.spawn_interval)
+            .max(MIN_TIME_STEP);
+        let mut ret = false;
+        let mut steps = 0;
+        while self.lag >= step && self.is_playing() {
+            if steps == MAX_STEPS_PER_TICK {
+                self.lag = 0.0;
+                break;
+            }
+            self.lag -= step;
+            ret |= self.step(step);
+            steps += 1;
+        }
+        ret
+    }
+
+    fn step(&mut self, delta_time: f64) -> bool {
+        if self.power_ups.tick(delta_time) {
+            self.timer.set_scale(1.0);
+        }
         let timer_msg = self.timer.tick(delta_time);
 
         let mut ret = false;
+        if let Some((direction, repeats)) = self
+            .auto_shift
+            .tick(delta_time, self.board.width() as usize)
+        {
+            for _ in 0..repeats {
+                let moved = match direction {
+                    Direction::Left => self.left(),
+                    Direction::Right => self.right(),
+                };
+                if !moved {
+                    break;
+                }
+                ret = true;
+            }
+        }
         if timer_msg.should_fall() {
             ret = true;
             use board::Msg as M;
             match self.board.fall_tick(timer_msg.should_drift()) {
-                Some(M::GameOver) => self.state = State::GameOver,
-                Some(M::BlocksSettled) => {
-                    let cleared = self.board.clear_completed();
-                    self.score += cleared
-                        .iter()
-                        .map(|b| b.position.y)
-                        .collect::<BTreeSet<_>>()
-                        .len()
+                Some(M::GameOver) => self.game_over(),
+                Some(M::BlocksSettled(blocks)) => {
+                    self.notices
+                        .extend(blocks.into_iter().map(Notice::BlockSettled));
+                    self.settle();
                 }
                 Some(M::Updated) => (),
                 None => ret = false,
             }
         }
-        if timer_msg.should_spawn() {
-            self.board.spawn_block();
+        if timer_msg.should_spawn() && self.spawn() {
+            self.notices.push(Notice::BlockSpawned);
             ret = true;
             if self.board.find_max_y(self.board.blocks().len() - 1) == 0 {
-                self.state = State::GameOver;
+                self.game_over();
             }
         }
+        if self.is_playing() {
+            // The last words may be cleared away without being typed.
+            self.check_finished();
+        }
 
         ret
     }
 
+    /// Clear and score the rows completed by blocks that just settled.
+    fn settle(&mut self) {
+        let cleared = self.board.clear_completed();
+        let level = self.level();
+        let rows = self.notify_cleared(cleared);
+        self.score += rows;
+        self.stats.record_rows(rows);
+        if self.level() > level {
+            self.notices.push(Notice::LevelUp(self.level()));
+        }
+        if rows == 0 {
+            self.raise_garbage();
+        } else {
+            self.send_garbage(rows);
+        }
+    }
+
+    /// Spawn a block with a word that isn't one of the [`Settings::repeat_window`] last, as hard
+    /// as [`Settings::word_difficulty`] asks for on this level, and sized to the stack as
+    /// [`Settings::word_sizing`] asks for, as far as there are words that are. Returns whether
+    /// anything spawned.
+    fn spawn(&mut self) -> bool {
+        let recent = &self.recent_words;
+        let band = self.settings.word_difficulty.band(self.level());
+        let gaps = self.board.open_gaps();
+        let sizing = self.settings.word_sizing;
+        let fresh = |w: &str| !recent.contains(w);
+        let in_band = |w: &str| band.contains(&difficulty(w));
+        let sized = |w: &str| sizing.suits(w.len(), &gaps);
+        if !self.board.spawn_block(
+            &mut self.rng,
+            &self.settings,
+            &mut self.words,
+            &[&fresh, &in_band, &sized],
+        ) {
+            return false;
+        }
+        let block = self.board.blocks().last().unwrap();
+        if block.kind() != block::Kind::Wildcard {
+            self.recent_words.push(block.assigned_text());
+        }
+        true
+    }
+
+    /// Queue a notice for the rows `cleared` came from, if any, and return how many there were.
+    fn notify_cleared(&mut self, cleared: Vec<Block>) -> usize {
+        let rows = cleared
+            .iter()
+            .map(|b| b.position.y)
+            .collect::<BTreeSet<_>>();
+        let len = rows.len();
+        if len > 0 {
+            self.notices.push(Notice::RowsCleared {
+                rows: rows.into_iter().collect(),
+                blocks: cleared,
+            });
+        }
+        len
+    }
+
+    #[inline]
+    fn game_over(&mut self) {
+        self.state = State::GameOver;
+        self.notices.push(Notice::GameOver);
+    }
+
+    /// Take the notices queued since they were last taken, oldest first.
+    #[inline]
+    pub fn take_notices(&mut self) -> Vec<Notice> {
+        std::mem::take(&mut self.notices)
+    }
+
+    /// Level the game is on, counting from 1. It goes up every [`ROWS_PER_LEVEL`] rows cleared.
+    #[inline]
+    pub fn level(&self) -> usize {
+        self.stats.rows() / ROWS_PER_LEVEL + 1
+    }
+
+    /// Number of garbage rows sent to the opponent for clearing `rows` rows at once.
+    #[inline]
+    pub fn garbage_for(rows: usize) -> usize {
+        match rows {
+            0 | 1 => 0,
+            2 => 1,
+            3 => 2,
+            n => n,
+        }
+    }
+
+    /// Queue garbage rows from the opponent. They rise the next time a block settles without
+    /// clearing anything.
+    #[inline]
+    pub(super) fn receive_garbage(&mut self, rows: usize) {
+        self.incoming_garbage += rows;
+    }
+
+    /// Take the garbage rows that are waiting to be sent to the opponent.
+    #[inline]
+    pub(super) fn take_garbage(&mut self) -> usize {
+        std::mem::take(&mut self.outgoing_garbage)
+    }
+
+    /// Cancel incoming garbage with garbage earned by clearing `rows` rows, and queue whatever
+    /// is left over to be sent.
+    fn send_garbage(&mut self, rows: usize) {
+        let sent = Self::garbage_for(rows);
+        let cancelled = sent.min(self.incoming_garbage);
+        self.incoming_garbage -= cancelled;
+        self.outgoing_garbage += sent - cancelled;
+    }
+
+    fn raise_garbage(&mut self) {
+        if self.incoming_garbage == 0 {
+            return;
+        }
+        let rows = self.incoming_garbage.min(self.board.height() as usize) as u8;
+        self.incoming_garbage = 0;
+        let gap = self.rng.random_range(0..self.board.width());
+        if let Some(board::Msg::GameOver) = self.board.add_garbage(rows, gap) {
+            self.game_over();
+        }
+    }
+
     #[inline]
     fn add_char(&mut self, ch: char) -> bool {
-        if let Some(focus) = self.board.get_focused_mut() {
-            focus.add_char(ch)
+        let Some(focus) = self.board.get_focused_mut() else {
+            return false;
+        };
+        let index = focus.input_text().len();
+        let correct = index < focus.assigned_text().len() && focus.matches(index, ch);
+        let expected = focus.assigned_text().chars().nth(index);
+        let previous = index
+            .checked_sub(1)
+            .and_then(|i| focus.assigned_text().chars().nth(i));
+        if focus.add_char(ch) {
+            // Wildcards take any letter, so there's nothing to learn from them.
+            if let Some(expected) = expected
+                && focus.kind() != block::Kind::Wildcard
+            {
+                self.words.record_keystroke(expected, previous, correct);
+            }
+            self.notices.push(Notice::Typed { correct });
+            self.stats.record_keystroke(correct);
+            if !correct {
+                self.power_ups.record_mistake();
+            }
+            true
         } else {
             false
         }
     }
 
+    /// Whether `ch` is the next character of the focused block's word.
+    fn expects(&self, ch: char) -> bool {
+        self.board.get_focused().is_some_and(|focus| {
+            focus
+                .assigned_text()
+                .get(focus.input_text().len()..)
+                .is_some_and(|rest| rest.starts_with(ch))
+        })
+    }
+
     #[inline]
     fn delete_char(&mut self) -> bool {
-        if let Some(focus) = self.board.get_focused_mut() {
-            focus.delete_char()
+        if let Some(focus) = self.board.get_focused_mut()
+            && focus.delete_char()
+        {
+            self.power_ups.record_mistake();
+            true
         } else {
             false
         }
@@ -171,7 +586,42 @@ impl Game {
 
     #[inline]
     fn focus_next(&mut self) -> bool {
-        self.board.focus_next()
+        let Some(focus) = self.board.get_focused() else {
+            return false;
+        };
+        let unlocking = focus.is_unlocking();
+        let correct = focus.is_correct();
+        // Wildcards aren't one of the source's words.
+        let from_words = focus.kind() != block::Kind::Wildcard;
+        if correct {
+            self.stats.record_word();
+            if from_words {
+                self.words.record_word();
+            }
+        }
+        self.power_ups.record_word(correct, &mut self.power_up_rng);
+        let ret = self.board.focus_next();
+        if ret {
+            self.notices.push(Notice::Dropped);
+        }
+        if unlocking {
+            self.settle();
+        }
+        self.check_finished();
+        ret
+    }
+
+    /// Finish the game if it reached its word goal, or if every word it had has been typed.
+    fn check_finished(&mut self) {
+        let reached_goal = self
+            .settings
+            .word_goal
+            .is_some_and(|goal| self.stats.words() >= goal);
+        let out_of_words = self.words.is_exhausted() && self.board.get_focused().is_none();
+        if reached_goal || out_of_words {
+            self.state = State::Finished;
+            self.notices.push(Notice::Finished);
+        }
     }
 
     #[inline]
@@ -184,11 +634,63 @@ impl Game {
         self.board.right()
     }
 
+    #[inline]
+    fn rotate(&mut self) -> bool {
+        self.board.rotate()
+    }
+
+    /// Use the power-up in slot `index`. It's only used up if it did something.
+    fn use_power_up(&mut self, index: usize) -> bool {
+        let Some(power_up) = self.power_ups.get(index) else {
+            return false;
+        };
+        let used = match power_up {
+            PowerUp::SlowTime => {
+                self.power_ups.start_slow_time();
+                self.timer.set_scale(power_up::SLOW_TIME_SCALE);
+                true
+            }
+            PowerUp::ClearBottomRow => {
+                let cleared = self.board.clear_row(self.board.height() - 1);
+                self.notify_cleared(cleared) > 0
+            }
+            PowerUp::SkipWord => self.board.remove_focused().is_some(),
+            PowerUp::ShrinkWord => self.board.shrink_focused(&mut self.power_up_rng),
+        };
+        if used {
+            self.power_ups.remove(index);
+        }
+        used
+    }
+
+    /// Fraction of the way the game is to the next fall, for drawing falling blocks between cells.
+    #[inline]
+    pub fn fall_progress(&self) -> f64 {
+        self.timer.fall_progress(self.lag)
+    }
+
+    /// How far below its cell the block at `index` should be drawn: the fall progress if it will
+    /// move down on the next fall, or zero if it won't.
+    pub fn fall_offset(&self, index: usize) -> f64 {
+        let block = &self.board.blocks()[index];
+        let falls = self.is_playing()
+            && !block.is_settled()
+            && (!block.is_interactable() || self.timer.drifts_next())
+            && self.board.find_max_y(index) > block.position.y;
+        if falls { self.fall_progress() } else { 0.0 }
+    }
+
     #[inline]
     pub fn is_game_over(&self) -> bool {
         self.state == State::GameOver
     }
 
+    /// Whether the game reached its [`Settings::word_goal`] or ran out of words.
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.state == State::Finished
+    }
+
     #[inline]
     pub fn is_playing(&self) -> bool {
         self.state == State::Playing
@@ -201,7 +703,9 @@ impl Game {
 
     #[inline]
     fn new_game(&mut self) {
-        *self = Self::new(self.settings);
+        let mut words = std::mem::take(&mut self.words);
+        words.restart();
+        *self = Self::with_words(self.settings, words);
     }
 }
 
@@ -218,7 +722,10 @@ mod test {
 
     #[inline]
     fn fall_until_settled(game: &mut Game) {
-        while !matches!(game.board.fall_tick(true), Some(board::Msg::BlocksSettled)) {}
+        while !matches!(
+            game.board.fall_tick(true),
+            Some(board::Msg::BlocksSettled(_))
+        ) {}
     }
 
     #[inline]
@@ -368,7 +875,12 @@ mod test {
         use Event as E;
 
         for _ in 0..4 {
-            let text = *game.board.get_focused().unwrap().assigned_text();
+            let text = game
+                .board
+                .get_focused()
+                .unwrap()
+                .assigned_text()
+                .to_string();
             for ch in text.chars() {
                 game.handle_event(E::Type(ch));
             }
@@ -537,6 +1049,287 @@ mod test {
         assert_eq!(game.state, State::GameOver);
     }
 
+    #[test]
+    fn stats() {
+        let mut game = Game::new(Settings::default().with_starts_with_one(false));
+        game.board.push_block(Block::with_text_x("ace", 0));
+        for event in [
+            Event::Type('a'),
+            Event::Type('x'),
+            Event::Delete,
+            Event::Type('c'),
+            Event::Type('e'),
+            Event::Type('s'),
+            Event::Next,
+        ] {
+            game.handle_event(event);
+        }
+        for _ in 0..6 {
+            game.handle_event(Event::Tick(1_000.0));
+        }
+        let stats = game.stats();
+        assert_eq!(stats.keystrokes(), 4);
+        assert_eq!(stats.correct_keystrokes(), 3);
+        assert_eq!(stats.words(), 1);
+        assert_eq!(stats.elapsed(), 6_000.0);
+        assert_eq!(stats.wpm(), 6.0);
+    }
+
+    #[test]
+    fn practice() {
+        let settings = Settings::default().with_starts_with_one(false);
+        let mut game = Game::with_words(settings, WordSource::Practice(Default::default()));
+        game.board.push_block(Block::with_text_x("ace", 0));
+        for event in [
+            Event::Type('a'),
+            Event::Type('x'),
+            Event::Delete,
+            Event::Type('c'),
+        ] {
+            game.handle_event(event);
+        }
+        let profile = game.words().key_profile().unwrap();
+        assert_eq!(profile.letter('c').attempts, 2.0);
+        assert_eq!(profile.letter('c').misses, 1.0);
+        assert_eq!(profile.weak_letters(3), vec![('c', 1.0 / 6.0)]);
+        assert_eq!(profile.weak_bigrams(3), vec![("ac", 1.0 / 6.0)]);
+
+        // Words with missed letters in them come up more often.
+        let count_c = |words: WordSource| {
+            let mut game = Game::with_words(settings.with_seed(Some(7)), words);
+            (0..200)
+                .filter(|_| {
+                    let mut board = Board::new(settings.width, settings.height);
+                    board.spawn_block(&mut game.rng, &game.settings, &mut game.words, &[]);
+                    board.blocks()[0].assigned_text().contains('c')
+                })
+                .count()
+        };
+        assert!(count_c(game.words.clone()) > 2 * count_c(WordSource::Random));
+
+        // What was learned carries over into the next game as it is. It only fades once a
+        // session, which is up to whoever keeps the profile between sessions.
+        for _ in 0..3 {
+            game.handle_event(Event::NewGame);
+        }
+        let profile = game.words().key_profile().unwrap();
+        assert_eq!(profile.letter('c').misses, 1.0);
+    }
+
+    /// Spawn a block on an empty board, and return its word.
+    fn spawn_alone(game: &mut Game) -> String {
+        game.board = Board::new(game.settings.width, game.settings.height);
+        assert!(game.spawn());
+        game.board.blocks()[0].assigned_text().to_string()
+    }
+
+    #[test]
+    fn word_difficulty() {
+        use difficulty::{WordDifficulty, difficulty};
+
+        let settings = Settings::default()
+            .with_seed(Some(11))
+            .with_word_difficulty(WordDifficulty::ByLevel);
+        let mut game = Game::new(settings);
+        for _ in 0..50 {
+            let word = spawn_alone(&mut game);
+            assert!(
+                settings
+                    .word_difficulty
+                    .band(1)
+                    .contains(&difficulty(&word))
+            );
+        }
+        game.stats.record_rows(19 * ROWS_PER_LEVEL);
+        assert_eq!(game.level(), 20);
+        for _ in 0..50 {
+            let word = spawn_alone(&mut game);
+            assert!(
+                settings
+                    .word_difficulty
+                    .band(20)
+                    .contains(&difficulty(&word))
+            );
+        }
+
+        // With no words in the band, any word will do.
+        game.settings = settings.with_word_difficulty(WordDifficulty::Band { min: 2.0, max: 3.0 });
+        spawn_alone(&mut game);
+    }
+
+    #[test]
+    fn word_sizing() {
+        use board::WordSizing;
+
+        // Gaps 8 wide on top of the settled word, and 4 wide next to it.
+        let lengths = |sizing| {
+            let settings = Settings::default()
+                .with_seed(Some(2))
+                .with_word_sizing(sizing);
+            let mut game = Game::new(settings);
+            (0..100)
+                .map(|_| {
+                    game.board = Board::new(settings.width, settings.height);
+                    let settled = Block::new_settled("abcdefgh", 0, settings.height - 1);
+                    game.board.push_block(settled);
+                    assert!(game.spawn());
+                    game.board.blocks()[1].assigned_text().len()
+                })
+                .collect::<Vec<_>>()
+        };
+        assert!(
+            lengths(WordSizing::FillGaps)
+                .iter()
+                .all(|&len| len == 4 || len == 8)
+        );
+        assert!(
+            lengths(WordSizing::AvoidGaps)
+                .iter()
+                .all(|&len| len != 4 && len != 8)
+        );
+        assert!(lengths(WordSizing::Any).contains(&4));
+    }
+
+    #[test]
+    fn no_repeats() {
+        const WINDOW: usize = 40;
+        for seed in 0..3 {
+            let settings = Settings::default()
+                .with_seed(Some(seed))
+                .with_shaped_pieces(true)
+                .with_special_block_chance(0.2)
+                .with_repeat_window(WINDOW);
+            for words in [WordSource::Random, WordSource::Practice(Default::default())] {
+                let mut game = Game::with_words(settings, words);
+                let spawned: Vec<String> = (0..300).map(|_| spawn_alone(&mut game)).collect();
+                for (i, word) in spawned.iter().enumerate().filter(|(_, w)| *w != "?") {
+                    let window = &spawned[i.saturating_sub(WINDOW)..i];
+                    assert!(!window.contains(word), "{word} repeated, seed {seed}");
+                }
+            }
+        }
+
+        // Without a window, words come back soon enough.
+        let settings = Settings::default().with_seed(Some(0)).with_repeat_window(0);
+        let mut game = Game::new(settings);
+        let spawned: Vec<String> = (0..100).map(|_| spawn_alone(&mut game)).collect();
+        assert!(
+            (1..spawned.len()).any(|i| spawned[i.saturating_sub(WINDOW)..i].contains(&spawned[i]))
+        );
+
+        // With a window longer than the list, words repeat rather than running out.
+        let mut game = Game::new(Settings::default().with_repeat_window(1_000));
+        for _ in 0..300 {
+            spawn_alone(&mut game);
+        }
+    }
+
+    #[test]
+    fn fixed_steps() {
+        let settings = Settings::default()
+            .with_seed(Some(5))
+            .with_spawn_interval(300.0)
+            .with_fall_interval(20.0)
+            .with_drift_interval(1)
+            .with_time_step(5.0);
+        let mut a = Game::new(settings);
+        let mut b = Game::new(settings);
+        let mut c = Game::new(settings);
+        // The same time in big and small frames plays out the same, and no falls are lost.
+        for _ in 0..30 {
+            a.handle_event(Event::Tick(100.0));
+            for _ in 0..4 {
+                b.handle_event(Event::Tick(25.0));
+            }
+        }
+        for _ in 0..3 {
+            c.handle_event(Event::Tick(1_000.0));
+        }
+        assert_eq!(a, b);
+        assert_eq!(a, c);
+
+        // Catching up is capped.
+        let mut d = Game::new(settings);
+        d.handle_event(Event::Tick(60_000.0));
+        assert_eq!(d.stats().elapsed(), settings.max_catch_up);
+    }
+
+    #[test]
+    fn min_step() {
+        for interval in [0.0, -1.0, f64::NAN] {
+            let settings = Settings::default()
+                .with_seed(Some(5))
+                .with_time_step(interval)
+                .with_fall_interval(interval)
+                .with_spawn_interval(interval);
+            let mut game = Game::new(settings);
+            // Ticks still end, in steps no shorter than the shortest allowed.
+            game.handle_event(Event::Tick(10.0));
+            assert!(game.lag < MIN_TIME_STEP || !game.is_playing());
+        }
+
+        // However many steps fit, a tick only simulates so many, and drops the rest.
+        let settings = Settings::default()
+            .with_seed(Some(5))
+            .with_time_step(MIN_TIME_STEP)
+            .with_spawn_interval(60_000.0);
+        let mut game = Game::new(settings);
+        game.handle_event(Event::Tick(settings.max_catch_up));
+        assert!(game.is_playing());
+        assert_eq!(game.lag, 0.0);
+        let mut capped = Game::new(settings);
+        capped.handle_event(Event::Tick(MAX_STEPS_PER_TICK as f64 * MIN_TIME_STEP));
+        assert_eq!(game.board, capped.board);
+    }
+
+    #[test]
+    fn seeded() {
+        let settings = Settings::default()
+            .with_seed(Some(42))
+            .with_spawn_interval(1.0)
+            .with_fall_interval(1.0);
+        let mut a = Game::new(settings);
+        let mut b = Game::new(settings);
+        assert_eq!(a, b);
+        for _ in 0..20 {
+            a.handle_event(Event::Tick(1.0));
+            b.handle_event(Event::Tick(1.0));
+        }
+        assert_eq!(a.board, b.board);
+        assert_eq!(a.state_hash(), b.state_hash());
+
+        a.handle_event(Event::NewGame);
+        assert_eq!(a, Game::new(settings));
+        assert_ne!(a.state_hash(), b.state_hash());
+    }
+
+    #[test]
+    fn power_ups_keep_spawns() {
+        let settings = Settings::default()
+            .with_starts_with_one(false)
+            .with_seed(Some(9))
+            .with_spawn_interval(60_000.0);
+        let mut flawless = Game::new(settings);
+        let mut sloppy = Game::new(settings);
+        for _ in 0..power_up::STREAK_LENGTH * 3 {
+            let word = spawn_alone(&mut flawless);
+            assert_eq!(spawn_alone(&mut sloppy), word);
+            // A typo fixed straight away breaks the streak, so only one game earns power-ups.
+            let typo = if word.starts_with('a') { 'b' } else { 'a' };
+            sloppy.handle_event(Event::Type(typo));
+            sloppy.handle_event(Event::Delete);
+            for ch in word.chars() {
+                flawless.handle_event(Event::Type(ch));
+                sloppy.handle_event(Event::Type(ch));
+            }
+            flawless.handle_event(Event::Next);
+            sloppy.handle_event(Event::Next);
+        }
+        assert!(!flawless.power_ups().inventory().is_empty());
+        assert!(sloppy.power_ups().inventory().is_empty());
+        assert_eq!(spawn_alone(&mut flawless), spawn_alone(&mut sloppy));
+    }
+
     #[test]
     fn splash() {
         let settings = Settings::default()
@@ -558,4 +1351,185 @@ mod test {
         assert!(game.handle_event(Event::Tick(1.1)));
         assert!(game.handle_event(Event::Type('a')));
     }
+
+    #[test]
+    fn power_ups() {
+        let settings = Settings::default()
+            .with_starts_with_one(false)
+            .with_width(8)
+            .with_height(4)
+            .with_seed(Some(3))
+            .with_spawn_interval(60_000.0)
+            .with_fall_interval(10.0);
+        let mut game = Game::new(settings);
+        for i in 0..power_up::STREAK_LENGTH {
+            game.board.push_block(Block::new_interactable("ab", 0, 0));
+            game.handle_event(Event::Type('a'));
+            if i == 0 {
+                // Fixing a typo still breaks the streak.
+                game.handle_event(Event::Type('x'));
+                game.handle_event(Event::Delete);
+            }
+            game.handle_event(Event::Type('b'));
+            game.handle_event(Event::Next);
+            game.board = Board::new(8, 4);
+        }
+        assert_eq!(game.power_ups().streak(), power_up::STREAK_LENGTH - 1);
+        assert!(game.power_ups().inventory().is_empty());
+        assert!(!game.handle_event(Event::UsePowerUp(0)));
+
+        game.board.push_block(Block::new_settled("abc", 0, 3));
+        game.board.push_block(Block::new_settled("de", 0, 2));
+        game.board
+            .push_block(Block::new_interactable("planet", 0, 0));
+        game.power_ups.give(PowerUp::ShrinkWord);
+        game.power_ups.give(PowerUp::ClearBottomRow);
+        game.power_ups.give(PowerUp::SkipWord);
+        game.power_ups.give(PowerUp::SlowTime);
+
+        assert!(game.handle_event(Event::UsePowerUp(0)));
+        assert!(game.board.get_focused().unwrap().assigned_text().len() < 6);
+        assert!(game.handle_event(Event::UsePowerUp(0)));
+        assert_eq!(game.board.blocks()[0], Block::new_falling("de", 0, 2));
+        assert!(game.handle_event(Event::UsePowerUp(0)));
+        assert!(game.board.get_focused().is_none());
+        assert!(!game.handle_event(Event::UsePowerUp(1)));
+
+        // Half speed: one fall every 20 instead of every 10.
+        assert!(game.handle_event(Event::UsePowerUp(0)));
+        assert!(game.power_ups().inventory().is_empty());
+        assert!(!game.handle_event(Event::Tick(15.0)));
+        assert!(game.handle_event(Event::Tick(5.0)));
+        for _ in 0..(power_up::SLOW_TIME_DURATION / 1_000.0) as usize {
+            game.handle_event(Event::Tick(1_000.0));
+        }
+        assert_eq!(game.power_ups().slow_time(), 0.0);
+        game.board.push_block(Block::new_falling("fg", 4, 0));
+        assert!(game.handle_event(Event::Tick(10.0)));
+    }
+
+    #[test]
+    fn notices() {
+        let settings = Settings::default()
+            .with_starts_with_one(false)
+            .with_width(4)
+            .with_height(4)
+            .with_spawn_interval(60_000.0)
+            .with_fall_interval(1.0)
+            .with_drift_interval(1)
+            .with_time_step(1.0);
+        let mut game = Game::new(settings);
+        game.board.push_block(Block::new_interactable("ab", 0, 0));
+        for event in [
+            Event::Type('a'),
+            Event::Type('x'),
+            Event::Delete,
+            Event::Type('b'),
+            Event::Next,
+        ] {
+            game.handle_event(event);
+        }
+        assert_eq!(
+            game.take_notices(),
+            vec![
+                Notice::Typed { correct: true },
+                Notice::Typed { correct: false },
+                Notice::Typed { correct: true },
+                Notice::Dropped,
+            ]
+        );
+
+        let mut game = Game::new(settings);
+        game.stats.record_rows(ROWS_PER_LEVEL - 1);
+        game.board.push_block(Block::new_settled("ab", 0, 3));
+        game.board.push_block(Block::new_falling("cd", 2, 2));
+        game.handle_event(Event::Tick(2.0));
+        assert_eq!(
+            game.take_notices(),
+            vec![
+                Notice::BlockSettled(Block::new_settled("cd", 2, 3)),
+                Notice::RowsCleared {
+                    rows: vec![3],
+                    blocks: vec![
+                        Block::new_settled("ab", 0, 3),
+                        Block::new_settled("cd", 2, 3)
+                    ],
+                },
+                Notice::LevelUp(2),
+            ]
+        );
+        assert!(game.take_notices().is_empty());
+
+        game.board.push_block(Block::new_settled("ef", 0, 1));
+        game.board.push_block(Block::new_falling("gh", 0, 0));
+        game.handle_event(Event::Tick(1.0));
+        assert_eq!(game.take_notices(), vec![Notice::GameOver]);
+    }
+
+    #[test]
+    fn interpolation() {
+        let settings = Settings::default()
+            .with_starts_with_one(false)
+            .with_spawn_interval(60_000.0)
+            .with_fall_interval(100.0)
+            .with_drift_interval(2)
+            .with_time_step(5.0);
+        let mut game = Game::new(settings);
+        game.board.push_block(Block::new_interactable("ab", 0, 0));
+        game.board.push_block(Block::new_falling("cd", 4, 0));
+        game.board.push_block(Block::new_settled("ef", 8, 15));
+
+        // Time that doesn't make up a whole step still counts.
+        game.handle_event(Event::Tick(52.0));
+        assert!((game.fall_progress() - 0.52).abs() < 1e-9);
+        // The focused block only falls on every other fall here, and settled blocks never do.
+        assert_eq!(game.fall_offset(0), 0.0);
+        assert_eq!(game.fall_offset(1), game.fall_progress());
+        assert_eq!(game.fall_offset(2), 0.0);
+        game.handle_event(Event::Tick(50.0));
+        assert_eq!(game.fall_offset(0), game.fall_progress());
+    }
+
+    #[test]
+    fn auto_shift() {
+        let settings = Settings::default()
+            .with_starts_with_one(false)
+            .with_width(8)
+            .with_spawn_interval(60_000.0)
+            .with_fall_interval(60_000.0)
+            .with_time_step(1.0)
+            .with_auto_shift_delay(170.0)
+            .with_auto_repeat_interval(50.0);
+        let mut game = Game::new(settings);
+        game.board.push_block(Block::new_interactable("ab", 0, 0));
+        let x = |game: &Game| game.board.get_focused().unwrap().position.x;
+
+        // Nothing moves until the word is typed, but the key is still held.
+        assert!(!game.handle_event(Event::RightPressed));
+        game.handle_event(Event::Type('a'));
+        game.handle_event(Event::Type('b'));
+        assert!(game.handle_event(Event::Tick(170.0)));
+        assert_eq!(x(&game), 1);
+
+        assert!(game.handle_event(Event::RightPressed));
+        assert_eq!(x(&game), 2);
+        assert!(!game.handle_event(Event::Tick(169.0)));
+        assert!(game.handle_event(Event::Tick(1.0)));
+        assert_eq!(x(&game), 3);
+        assert!(game.handle_event(Event::Tick(100.0)));
+        assert_eq!(x(&game), 5);
+        assert!(!game.handle_event(Event::RightReleased));
+        assert!(!game.handle_event(Event::Tick(1_000.0)));
+        assert_eq!(x(&game), 5);
+
+        // Held against the wall.
+        game.handle_event(Event::RightPressed);
+        assert!(!game.handle_event(Event::Tick(1_000.0)));
+        assert_eq!(x(&game), 6);
+
+        game.auto_shift = AutoShift::new(170.0, 0.0);
+        game.handle_event(Event::LeftPressed);
+        game.handle_event(Event::Tick(170.0));
+        assert_eq!(x(&game), 0);
+    }
 }
@@ -0,0 +1,244 @@
+use getset::CopyGetters;
+
+use super::{Event, Game, Notice, settings::Settings, versus::Player};
+use crate::protocol::{HASH_INTERVAL, Message};
+
+/// One side of a versus match played over the network.
+///
+/// The local game is driven by the local player's events, and the opponent's game is simulated
+/// from the messages they stream. Garbage isn't taken on the opponent's word: the local copy of
+/// their game works out what they send, and the local game records in its own stream when that
+/// garbage is queued, so the opponent can replay it at exactly the same point.
+#[derive(Debug, Clone, PartialEq, CopyGetters)]
+pub struct OnlineMatch {
+    /// The seat of the local player.
+    #[getset(get_copy = "pub")]
+    player: Player,
+    games: [Game; 2],
+    local_frame: u32,
+    remote_frame: u32,
+    /// Whether the opponent reported a hash that doesn't match our copy of their game.
+    #[getset(get_copy = "pub")]
+    desynced: bool,
+    #[getset(get_copy = "pub")]
+    winner: Option<Player>,
+    outbox: Vec<Message>,
+}
+
+impl OnlineMatch {
+    /// Start a match from the [`Message::Start`] sent by the server.
+    pub fn new(settings: Settings, seed: u64, player: Player) -> Self {
+        let settings = settings
+            .with_starts_with_splash(false)
+            .with_seed(Some(seed));
+        Self {
+            player,
+            games: [Game::new(settings), Game::new(settings)],
+            local_frame: 0,
+            remote_frame: 0,
+            desynced: false,
+            winner: None,
+            outbox: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn game(&self, player: Player) -> &Game {
+        &self.games[player.index()]
+    }
+
+    #[inline]
+    pub fn is_over(&self) -> bool {
+        self.winner.is_some()
+    }
+
+    /// Take the notices queued by the game of `player`, oldest first.
+    #[inline]
+    pub fn take_notices(&mut self, player: Player) -> Vec<Notice> {
+        self.games[player.index()].take_notices()
+    }
+
+    /// Take the messages that should be sent to the opponent, in order.
+    #[inline]
+    pub fn take_outbox(&mut self) -> Vec<Message> {
+        std::mem::take(&mut self.outbox)
+    }
+
+    /// Handle an event from the local player and return a boolean indicating whether state has
+    /// changed. [`Event::NewGame`] is ignored: a new match needs a new seed from the server.
+    pub fn handle_event(&mut self, event: Event) -> bool {
+        if self.is_over() || matches!(event, Event::NewGame) {
+            return false;
+        }
+        let local = &mut self.games[self.player.index()];
+        let ret = local.handle_event(event);
+        // The opponent works out what we send from their copy of our game.
+        local.take_garbage();
+        self.record(Message::Input { event });
+        ret
+    }
+
+    /// Handle a message relayed from the opponent and return a boolean indicating whether state
+    /// has changed.
+    pub fn handle_message(&mut self, message: Message) -> bool {
+        if self.is_over() {
+            return false;
+        }
+        let opponent = self.player.opponent();
+        match message {
+            Message::Input { event } => {
+                self.remote_frame += 1;
+                let remote = &mut self.games[opponent.index()];
+                let ret = remote.handle_event(event);
+                let rows = remote.take_garbage();
+                if rows > 0 {
+                    self.games[self.player.index()].receive_garbage(rows);
+                    self.record(Message::Garbage { rows });
+                }
+                ret || rows > 0
+            }
+            Message::Garbage { rows } => {
+                self.remote_frame += 1;
+                self.games[opponent.index()].receive_garbage(rows);
+                true
+            }
+            Message::StateHash { frame, hash } => {
+                if frame == self.remote_frame && hash != self.games[opponent.index()].state_hash() {
+                    self.desynced = true;
+                    true
+                } else {
+                    false
+                }
+            }
+            Message::GameOver | Message::OpponentLeft => {
+                self.winner = Some(self.player);
+                true
+            }
+            Message::Join { .. } | Message::Start { .. } => false,
+        }
+    }
+
+    fn record(&mut self, message: Message) {
+        self.outbox.push(message);
+        self.local_frame += 1;
+        let local = &self.games[self.player.index()];
+        if self.local_frame.is_multiple_of(HASH_INTERVAL) {
+            self.outbox.push(Message::StateHash {
+                frame: self.local_frame,
+                hash: local.state_hash(),
+            });
+        }
+        if local.is_game_over() {
+            self.outbox.push(Message::GameOver);
+            self.winner = Some(self.player.opponent());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::block::Block;
+
+    fn settings() -> Settings {
+        Settings::default()
+            .with_width(4)
+            .with_height(6)
+            .with_spawn_interval(100.0)
+            .with_fall_interval(1.0)
+            .with_drift_interval(1)
+    }
+
+    /// Deliver everything each side has sent to the other.
+    fn exchange(a: &mut OnlineMatch, b: &mut OnlineMatch) {
+        loop {
+            let to_b = a.take_outbox();
+            let to_a = b.take_outbox();
+            if to_a.is_empty() && to_b.is_empty() {
+                return;
+            }
+            for message in to_b {
+                b.handle_message(message);
+            }
+            for message in to_a {
+                a.handle_message(message);
+            }
+        }
+    }
+
+    #[test]
+    fn mirrors() {
+        let mut a = OnlineMatch::new(settings(), 7, Player::One);
+        let mut b = OnlineMatch::new(settings(), 7, Player::Two);
+        assert_eq!(a.game(Player::One), b.game(Player::One));
+        assert_eq!(a.game(Player::Two), b.game(Player::Two));
+
+        for i in 0..200 {
+            a.handle_event(Event::Tick(1.0));
+            if i % 3 == 0 {
+                b.handle_event(Event::Tick(1.0));
+                b.handle_event(Event::Next);
+            }
+            if i % 7 == 0 {
+                exchange(&mut a, &mut b);
+            }
+        }
+        exchange(&mut a, &mut b);
+        assert!(!a.desynced() && !b.desynced());
+        assert_eq!(a.game(Player::One), b.game(Player::One));
+        assert_eq!(a.game(Player::Two), b.game(Player::Two));
+        assert_eq!(a.winner(), b.winner());
+    }
+
+    #[test]
+    fn garbage_is_replayed() {
+        let mut a = OnlineMatch::new(settings().with_starts_with_one(false), 7, Player::One);
+        let mut b = OnlineMatch::new(settings().with_starts_with_one(false), 7, Player::Two);
+        // Set up the same double clear in both copies of player one's game.
+        for m in [&mut a, &mut b] {
+            let board = &mut m.games[0].board;
+            board.push_block(Block::new_settled("ab", 2, 5));
+            board.push_block(Block::new_settled("ef", 2, 4));
+            board.push_block(Block::new_falling("cd", 0, 4));
+            board.push_block(Block::new_falling("gh", 0, 3));
+        }
+        a.handle_event(Event::Tick(1.0));
+        a.handle_event(Event::Tick(1.0));
+        assert_eq!(a.game(Player::Two).incoming_garbage(), 0);
+
+        exchange(&mut a, &mut b);
+        assert_eq!(b.game(Player::Two).incoming_garbage(), 1);
+        assert_eq!(a.game(Player::Two).incoming_garbage(), 1);
+        assert_eq!(a.game(Player::Two), b.game(Player::Two));
+    }
+
+    #[test]
+    fn detects_desync() {
+        let mut a = OnlineMatch::new(settings(), 7, Player::One);
+        let mut b = OnlineMatch::new(settings(), 7, Player::Two);
+        // Nudge b's copy of player one's game so it no longer matches the real thing.
+        b.games[0].score += 1;
+        for _ in 0..HASH_INTERVAL {
+            a.handle_event(Event::Tick(0.1));
+        }
+        exchange(&mut a, &mut b);
+        assert!(b.desynced());
+        assert!(!a.desynced());
+    }
+
+    #[test]
+    fn game_over() {
+        let mut a = OnlineMatch::new(settings(), 7, Player::One);
+        let mut b = OnlineMatch::new(settings(), 7, Player::Two);
+        while !a.is_over() {
+            a.handle_event(Event::Tick(1.0));
+            a.handle_event(Event::Next);
+        }
+        assert_eq!(a.winner(), Some(Player::Two));
+        assert!(a.take_outbox().ends_with(&[Message::GameOver]));
+        assert!(!a.handle_event(Event::Tick(1.0)));
+
+        assert!(b.handle_message(Message::OpponentLeft));
+        assert_eq!(b.winner(), Some(Player::Two));
+    }
+}
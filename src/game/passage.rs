@@ -0,0 +1,236 @@
+use getset::WithSetters;
+
+/// Whether punctuation is typed as part of the word it's next to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Punctuation {
+    /// `"Hello,"` is one block.
+    #[default]
+    Attached,
+    /// `"Hello,"` is a block of `Hello` and one of `,`. Apostrophes inside words, like in
+    /// `don't`, stay.
+    Separate,
+}
+
+/// What to do with words too wide for the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LongWords {
+    /// Cut them into pieces as wide as the board.
+    #[default]
+    Split,
+    /// Leave them out.
+    Skip,
+}
+
+/// How a pasted text is cut into the words of blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, WithSetters)]
+pub struct TextOptions {
+    #[getset(set_with = "pub")]
+    pub punctuation: Punctuation,
+    #[getset(set_with = "pub")]
+    pub long_words: LongWords,
+}
+
+/// The words of a text, handed out in reading order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Passage {
+    words: Vec<String>,
+    /// Index of the next word to spawn.
+    next: usize,
+    /// How many of the words were typed right.
+    typed: usize,
+}
+
+/// `word` with typographic characters replaced by the ASCII ones they stand for, and anything
+/// else that can't be typed on a plain keyboard left out.
+fn to_ascii(word: &str) -> String {
+    let mut ret = String::with_capacity(word.len());
+    for ch in word.chars() {
+        match ch {
+            '‘' | '’' | '‚' | '′' => ret.push('\''),
+            '“' | '”' | '„' | '″' => ret.push('"'),
+            '‐' | '‑' | '‒' | '–' | '—' | '−' => ret.push('-'),
+            '…' => ret.push_str("..."),
+            ch if ch.is_ascii_graphic() => ret.push(ch),
+            _ => (),
+        }
+    }
+    ret
+}
+
+/// `word` cut between letters and digits on one side and punctuation on the other.
+fn split_punctuation(word: &str) -> Vec<String> {
+    let chars: Vec<char> = word.chars().collect();
+    let is_letter = |i: usize| {
+        let ch = chars[i];
+        ch.is_ascii_alphanumeric()
+            || (ch == '\''
+                && i > 0
+                && chars[i - 1].is_ascii_alphanumeric()
+                && chars.get(i + 1).is_some_and(char::is_ascii_alphanumeric))
+    };
+    let mut ret: Vec<String> = Vec::new();
+    for (i, &ch) in chars.iter().enumerate() {
+        if i > 0 && is_letter(i) == is_letter(i - 1) {
+            ret.last_mut().unwrap().push(ch);
+        } else {
+            ret.push(ch.to_string());
+        }
+    }
+    ret
+}
+
+/// Cut `text` into words of at most `width` characters.
+pub fn tokenize(text: &str, width: u8, options: TextOptions) -> Vec<String> {
+    let width = usize::from(width.max(1));
+    // Dashes between words are often written without spaces around them.
+    text.replace(['—', '–'], " — ")
+        .split_whitespace()
+        .map(to_ascii)
+        .flat_map(|word| match options.punctuation {
+            Punctuation::Attached => vec![word],
+            Punctuation::Separate => split_punctuation(&word),
+        })
+        .filter(|word| !word.is_empty())
+        .flat_map(|word| {
+            if word.len() <= width {
+                return vec![word];
+            }
+            match options.long_words {
+                // Everything left is ASCII, so every byte is a character.
+                LongWords::Split => word
+                    .as_bytes()
+                    .chunks(width)
+                    .map(|chunk| String::from_utf8_lossy(chunk).into_owned())
+                    .collect(),
+                LongWords::Skip => vec![],
+            }
+        })
+        .collect()
+}
+
+impl Passage {
+    /// The words of `text` for a board `width` cells wide.
+    pub fn new(text: &str, width: u8, options: TextOptions) -> Self {
+        Self {
+            words: tokenize(text, width, options),
+            next: 0,
+            typed: 0,
+        }
+    }
+
+    #[inline]
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// How many of the words have spawned so far.
+    #[inline]
+    pub fn spawned(&self) -> usize {
+        self.next
+    }
+
+    /// How many of the words were typed right.
+    #[inline]
+    pub fn typed(&self) -> usize {
+        self.typed
+    }
+
+    /// The fraction of the words typed right, from 0 to 1.
+    pub fn typed_fraction(&self) -> f64 {
+        if self.words.is_empty() {
+            return 0.0;
+        }
+        self.typed as f64 / self.words.len() as f64
+    }
+
+    /// Whether every word has spawned.
+    #[inline]
+    pub fn is_exhausted(&self) -> bool {
+        self.next >= self.words.len()
+    }
+
+    /// The next word, if it `fits`. Words are never skipped, so one that doesn't fit waits for
+    /// the next pick.
+    pub(super) fn pick(&mut self, fits: impl Fn(&str) -> bool) -> Option<String> {
+        let word = self.words.get(self.next).filter(|w| fits(w))?.clone();
+        self.next += 1;
+        Some(word)
+    }
+
+    /// Count one more word typed right.
+    pub(super) fn record_typed(&mut self) {
+        self.typed = (self.typed + 1).min(self.words.len());
+    }
+
+    /// Start again from the first word.
+    pub(super) fn restart(&mut self) {
+        self.next = 0;
+        self.typed = 0;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::settings::Settings;
+    use crate::game::{Event, Game, Notice, words::WordSource};
+
+    #[test]
+    fn t0() {
+        let text = "“Don’t panic,” she said…\n\tWell — supercalifragilistic! 日本";
+        let attached = TextOptions::default();
+        assert_eq!(
+            tokenize(text, 8, attached),
+            [
+                "\"Don't", "panic,\"", "she", "said...", "Well", "-", "supercal", "ifragili",
+                "stic!"
+            ]
+        );
+        let separate = attached.with_punctuation(Punctuation::Separate);
+        assert_eq!(
+            tokenize(text, 8, separate.with_long_words(LongWords::Skip)),
+            [
+                "\"", "Don't", "panic", ",\"", "she", "said", "...", "Well", "-", "!"
+            ]
+        );
+        assert_eq!(split_punctuation("'tis"), ["'", "tis"]);
+        assert_eq!(tokenize("ago—never", 8, attached), ["ago", "-", "never"]);
+        assert_eq!(tokenize("", 8, attached), Vec::<String>::new());
+    }
+
+    #[test]
+    fn in_order() {
+        let text = "the 3 quick, brown foxes jump";
+        let settings = Settings::default().with_seed(Some(1)).with_width(6);
+        let passage = Passage::new(text, settings.width, TextOptions::default());
+        let mut game = Game::with_words(settings, WordSource::Text(passage));
+        let mut typed = Vec::new();
+        while !game.is_finished() {
+            assert!(!game.is_game_over());
+            if let Some(focus) = game.board().get_focused() {
+                let word = focus.assigned_text().to_string();
+                for ch in word.chars() {
+                    // Digit keys use power-ups, unless the word needs a digit next.
+                    game.handle_event(match ch.to_digit(10) {
+                        Some(d) => Event::UsePowerUp(d as usize - 1),
+                        None => Event::Type(ch),
+                    });
+                }
+                game.handle_event(Event::Next);
+                typed.push(word);
+            }
+            game.handle_event(Event::Tick(1_000.0));
+        }
+        assert_eq!(typed.join(" "), text);
+        assert!(game.take_notices().contains(&Notice::Finished));
+        let WordSource::Text(passage) = game.words() else {
+            unreachable!()
+        };
+        assert_eq!(passage.typed(), 6);
+        assert_eq!(passage.typed_fraction(), 1.0);
+
+        game.handle_event(Event::NewGame);
+        let first = game.board().get_focused().unwrap().assigned_text();
+        assert_eq!(first, "the");
+    }
+}
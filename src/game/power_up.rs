@@ -0,0 +1,161 @@
+//! Power-ups, earned by typing words flawlessly: without a wrong character or a delete.
+
+use getset::{CopyGetters, Getters};
+use rand::{Rng, seq::IndexedRandom};
+use serde::{Deserialize, Serialize};
+
+/// Flawless words in a row it takes to earn a power-up.
+pub const STREAK_LENGTH: usize = 5;
+/// Most power-ups that can be held at once.
+pub const INVENTORY_SIZE: usize = 3;
+/// How long [`PowerUp::SlowTime`] lasts, in the same unit as [`super::Event::Tick`].
+pub const SLOW_TIME_DURATION: f64 = 10_000.0;
+/// How much [`PowerUp::SlowTime`] stretches the fall and spawn intervals.
+pub const SLOW_TIME_SCALE: f64 = 2.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PowerUp {
+    /// Everything falls and spawns at half speed for a while.
+    SlowTime,
+    /// Clear the bottom row, full or not.
+    ClearBottomRow,
+    /// Throw away the focused block.
+    SkipWord,
+    /// Swap the focused block's word for a shorter one.
+    ShrinkWord,
+}
+
+impl PowerUp {
+    pub const ALL: [Self; 4] = [
+        Self::SlowTime,
+        Self::ClearBottomRow,
+        Self::SkipWord,
+        Self::ShrinkWord,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::SlowTime => "Slow time",
+            Self::ClearBottomRow => "Clear bottom row",
+            Self::SkipWord => "Skip word",
+            Self::ShrinkWord => "Shrink word",
+        }
+    }
+}
+
+/// The power-ups a player holds, and their progress towards the next one.
+#[derive(Debug, Clone, PartialEq, Getters, CopyGetters)]
+pub struct PowerUps {
+    #[getset(get = "pub")]
+    inventory: Vec<PowerUp>,
+    /// Flawless words in a row so far.
+    #[getset(get_copy = "pub")]
+    streak: usize,
+    /// Whether the focused word has been typed without mistakes so far.
+    flawless: bool,
+    /// Time left on [`PowerUp::SlowTime`].
+    #[getset(get_copy = "pub")]
+    slow_time: f64,
+}
+
+impl Default for PowerUps {
+    fn default() -> Self {
+        Self {
+            inventory: Vec::with_capacity(INVENTORY_SIZE),
+            streak: 0,
+            flawless: true,
+            slow_time: 0.0,
+        }
+    }
+}
+
+impl PowerUps {
+    #[inline]
+    pub(super) fn record_mistake(&mut self) {
+        self.flawless = false;
+    }
+
+    /// Record a dropped word, and return the power-up it earned, if any.
+    pub(super) fn record_word(&mut self, correct: bool, rng: &mut impl Rng) -> Option<PowerUp> {
+        let flawless = std::mem::replace(&mut self.flawless, true);
+        if !(correct && flawless) {
+            self.streak = 0;
+            return None;
+        }
+        self.streak += 1;
+        if !self.streak.is_multiple_of(STREAK_LENGTH) || self.inventory.len() >= INVENTORY_SIZE {
+            return None;
+        }
+        let power_up = *PowerUp::ALL.choose(rng).unwrap();
+        self.inventory.push(power_up);
+        Some(power_up)
+    }
+
+    #[inline]
+    pub(super) fn get(&self, index: usize) -> Option<PowerUp> {
+        self.inventory.get(index).copied()
+    }
+
+    #[inline]
+    pub(super) fn remove(&mut self, index: usize) {
+        self.inventory.remove(index);
+    }
+
+    #[inline]
+    pub(super) fn start_slow_time(&mut self) {
+        self.slow_time = SLOW_TIME_DURATION;
+    }
+
+    /// Count down slow time, and return whether it has just run out.
+    pub(super) fn tick(&mut self, delta_time: f64) -> bool {
+        if self.slow_time <= 0.0 {
+            return false;
+        }
+        self.slow_time -= delta_time;
+        if self.slow_time <= 0.0 {
+            self.slow_time = 0.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+impl PowerUps {
+    #[inline]
+    pub(super) fn give(&mut self, power_up: PowerUp) {
+        self.inventory.push(power_up);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    #[test]
+    fn t0() {
+        let mut power_ups = PowerUps::default();
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..STREAK_LENGTH - 1 {
+            assert_eq!(power_ups.record_word(true, &mut rng), None);
+        }
+        assert!(power_ups.record_word(true, &mut rng).is_some());
+        assert_eq!(power_ups.inventory().len(), 1);
+
+        // A mistake or a wrong word breaks the streak.
+        power_ups.record_mistake();
+        assert_eq!(power_ups.record_word(true, &mut rng), None);
+        assert_eq!(power_ups.streak(), 0);
+        assert_eq!(power_ups.record_word(true, &mut rng), None);
+        assert_eq!(power_ups.record_word(false, &mut rng), None);
+        assert_eq!(power_ups.streak(), 0);
+
+        for _ in 0..STREAK_LENGTH * (INVENTORY_SIZE + 1) {
+            power_ups.record_word(true, &mut rng);
+        }
+        assert_eq!(power_ups.inventory().len(), INVENTORY_SIZE);
+    }
+}
@@ -0,0 +1,182 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::layout::{Finger, Layout, Row};
+
+/// How much of a profile is kept from one session to the next, so that keys the player has since
+/// got better at stop being picked on.
+const DECAY: f64 = 0.8;
+/// Tallies with fewer attempts than this after decaying are forgotten.
+const FORGET_BELOW: f64 = 0.5;
+/// Attempts every tally starts out with, all of them correct, so that one early miss doesn't make
+/// a key look hopeless.
+const PRIOR_ATTEMPTS: f64 = 4.0;
+/// How much more likely a word is for every whole miss rate of its letters and bigrams.
+const WEAKNESS_BIAS: f64 = 20.0;
+
+/// How often something was typed, and how often it was typed wrong. Counts are fractional
+/// because they decay.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Tally {
+    pub attempts: f64,
+    pub misses: f64,
+}
+
+impl Tally {
+    /// Fraction of attempts that were misses, pulled towards zero while there are few of them.
+    pub fn miss_rate(&self) -> f64 {
+        self.misses / (self.attempts + PRIOR_ATTEMPTS)
+    }
+
+    fn record(&mut self, correct: bool) {
+        self.attempts += 1.0;
+        self.misses += !correct as u8 as f64;
+    }
+}
+
+/// Which letters and pairs of letters a player tends to get wrong, kept across games to pick words
+/// that practise them.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct KeyProfile {
+    letters: BTreeMap<char, Tally>,
+    /// Keyed by the letter before and the letter typed, as a two letter string.
+    bigrams: BTreeMap<String, Tally>,
+}
+
+impl KeyProfile {
+    /// Record typing `expected`, which came after `previous` in its word, right or wrong. Case is
+    /// ignored.
+    pub(super) fn record(&mut self, expected: char, previous: Option<char>, correct: bool) {
+        let expected = expected.to_ascii_lowercase();
+        self.letters.entry(expected).or_default().record(correct);
+        if let Some(previous) = previous {
+            let bigram = [previous.to_ascii_lowercase(), expected].iter().collect();
+            self.bigrams.entry(bigram).or_default().record(correct);
+        }
+    }
+
+    /// Fade out what was recorded so far, to start a new session. Call it once per session, not
+    /// every game, or a few quick restarts wipe the profile out.
+    pub fn decay(&mut self) {
+        for tally in self.letters.values_mut().chain(self.bigrams.values_mut()) {
+            tally.attempts *= DECAY;
+            tally.misses *= DECAY;
+        }
+        self.letters.retain(|_, t| t.attempts >= FORGET_BELOW);
+        self.bigrams.retain(|_, t| t.attempts >= FORGET_BELOW);
+    }
+
+    pub fn letter(&self, letter: char) -> Tally {
+        self.letters
+            .get(&letter.to_ascii_lowercase())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Up to `n` letters that were missed, worst first, with their miss rates.
+    pub fn weak_letters(&self, n: usize) -> Vec<(char, f64)> {
+        weakest(self.letters.iter().map(|(&c, t)| (c, t)), n)
+    }
+
+    /// Up to `n` bigrams that were missed, worst first, with their miss rates.
+    pub fn weak_bigrams(&self, n: usize) -> Vec<(&str, f64)> {
+        weakest(self.bigrams.iter().map(|(b, t)| (b.as_str(), t)), n)
+    }
+
+    /// Letters typed with each finger on `layout`, added up. Fingers nothing was typed with are
+    /// left out.
+    pub fn by_finger(&self, layout: Layout) -> BTreeMap<Finger, Tally> {
+        self.grouped(|ch| Some(layout.key(ch)?.1))
+    }
+
+    /// Letters typed on each row of `layout`, added up. Rows nothing was typed on are left out.
+    pub fn by_row(&self, layout: Layout) -> BTreeMap<Row, Tally> {
+        self.grouped(|ch| Some(layout.key(ch)?.0))
+    }
+
+    fn grouped<K: Ord>(&self, group: impl Fn(char) -> Option<K>) -> BTreeMap<K, Tally> {
+        let mut ret = BTreeMap::<K, Tally>::new();
+        for (&ch, tally) in &self.letters {
+            if let Some(key) = group(ch) {
+                let sum = ret.entry(key).or_default();
+                sum.attempts += tally.attempts;
+                sum.misses += tally.misses;
+            }
+        }
+        ret
+    }
+
+    /// How likely `word` should be to come up, relative to a word with no weak letters in it.
+    pub fn weight(&self, word: &str) -> f64 {
+        let lower = word.to_ascii_lowercase();
+        let letters: f64 = lower.chars().map(|c| self.letter(c).miss_rate()).sum();
+        let bigrams: f64 = (1..lower.len())
+            .filter_map(|i| self.bigrams.get(lower.get(i - 1..=i)?))
+            .map(Tally::miss_rate)
+            .sum();
+        1.0 + WEAKNESS_BIAS * (letters + bigrams)
+    }
+}
+
+fn weakest<'a, K>(tallies: impl Iterator<Item = (K, &'a Tally)>, n: usize) -> Vec<(K, f64)> {
+    let mut ret: Vec<_> = tallies
+        .filter(|(_, t)| t.misses > 0.0)
+        .map(|(k, t)| (k, t.miss_rate()))
+        .collect();
+    ret.sort_by(|a, b| b.1.total_cmp(&a.1));
+    ret.truncate(n);
+    ret
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn t0() {
+        let mut profile = KeyProfile::default();
+        for _ in 0..4 {
+            profile.record('a', None, true);
+            profile.record('Q', Some('a'), false);
+        }
+        assert_eq!(
+            profile.letter('q'),
+            Tally {
+                attempts: 4.0,
+                misses: 4.0
+            }
+        );
+        assert_eq!(profile.letter('q').miss_rate(), 0.5);
+        assert_eq!(profile.weak_letters(5), vec![('q', 0.5)]);
+        assert_eq!(profile.weak_bigrams(5), vec![("aq", 0.5)]);
+        assert_eq!(profile.weight("be"), 1.0);
+        assert_eq!(profile.weight("quiz"), 11.0);
+        assert_eq!(profile.weight("aqua"), 21.0);
+
+        assert_eq!(
+            profile.by_finger(Layout::Qwerty).get(&Finger::LeftPinky),
+            Some(&Tally {
+                attempts: 8.0,
+                misses: 4.0
+            })
+        );
+        let by_row = profile.by_row(Layout::Dvorak);
+        assert_eq!(by_row.get(&Row::Home).unwrap().misses, 0.0);
+        assert_eq!(by_row.get(&Row::Bottom).unwrap().misses, 4.0);
+
+        profile.decay();
+        assert_eq!(profile.letter('q').attempts, 3.2);
+        assert!(profile.letter('q').miss_rate() < 0.5);
+
+        profile.record('z', None, true);
+        for _ in 0..4 {
+            profile.decay();
+        }
+        assert_eq!(profile.letter('z'), Tally::default());
+        assert!(profile.letter('q').attempts > 0.0);
+
+        let json = serde_json::to_string(&profile).unwrap();
+        assert_eq!(serde_json::from_str::<KeyProfile>(&json).unwrap(), profile);
+    }
+}
@@ -0,0 +1,133 @@
+use super::Game;
+use super::passage::{Passage, TextOptions};
+use super::settings::Settings;
+use super::words::WordSource;
+
+/// A passage from a public-domain book, speech or play, to type in reading order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quote {
+    pub title: &'static str,
+    pub author: &'static str,
+    pub text: &'static str,
+}
+
+pub const QUOTES: [Quote; 8] = [
+    Quote {
+        title: "Pride and Prejudice",
+        author: "Jane Austen",
+        text: "It is a truth universally acknowledged, that a single man in possession of a good \
+               fortune, must be in want of a wife.",
+    },
+    Quote {
+        title: "A Tale of Two Cities",
+        author: "Charles Dickens",
+        text: "It was the best of times, it was the worst of times, it was the age of wisdom, it \
+               was the age of foolishness, it was the epoch of belief, it was the epoch of \
+               incredulity, it was the season of Light, it was the season of Darkness, it was the \
+               spring of hope, it was the winter of despair.",
+    },
+    Quote {
+        title: "Moby-Dick",
+        author: "Herman Melville",
+        text: "Call me Ishmael. Some years ago—never mind how long precisely—having little or no \
+               money in my purse, and nothing particular to interest me on shore, I thought I \
+               would sail about a little and see the watery part of the world.",
+    },
+    Quote {
+        title: "Alice's Adventures in Wonderland",
+        author: "Lewis Carroll",
+        text: "Alice was beginning to get very tired of sitting by her sister on the bank, and of \
+               having nothing to do: once or twice she had peeped into the book her sister was \
+               reading, but it had no pictures or conversations in it, “and what is the use of a \
+               book,” thought Alice “without pictures or conversations?”",
+    },
+    Quote {
+        title: "The Sign of the Four",
+        author: "Arthur Conan Doyle",
+        text: "How often have I said to you that when you have eliminated the impossible, \
+               whatever remains, however improbable, must be the truth?",
+    },
+    Quote {
+        title: "Hamlet",
+        author: "William Shakespeare",
+        text: "To be, or not to be, that is the question: Whether 'tis nobler in the mind to \
+               suffer The slings and arrows of outrageous fortune, Or to take arms against a sea \
+               of troubles And by opposing end them.",
+    },
+    Quote {
+        title: "The Gettysburg Address",
+        author: "Abraham Lincoln",
+        text: "Four score and seven years ago our fathers brought forth on this continent, a new \
+               nation, conceived in Liberty, and dedicated to the proposition that all men are \
+               created equal.",
+    },
+    Quote {
+        title: "Walden",
+        author: "Henry David Thoreau",
+        text: "I went to the woods because I wished to live deliberately, to front only the \
+               essential facts of life, and see if I could not learn what it had to teach, and \
+               not, when I came to die, discover that I had not lived.",
+    },
+];
+
+impl Quote {
+    /// The words of this quote for a board `width` cells wide.
+    pub fn passage(&self, width: u8) -> Passage {
+        Passage::new(self.text, width, TextOptions::default())
+    }
+
+    /// A game of this quote's words, in order.
+    pub fn game(&self, settings: Settings) -> Game {
+        Game::with_words(settings, WordSource::Text(self.passage(settings.width)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::Event;
+
+    #[test]
+    fn t0() {
+        let settings = Settings::default().with_seed(Some(3));
+        for quote in QUOTES {
+            let passage = quote.passage(settings.width);
+            assert!(
+                passage
+                    .words()
+                    .iter()
+                    .all(|w| w.is_ascii() && w.len() <= settings.width as usize),
+                "{}",
+                quote.title
+            );
+        }
+
+        // Only the words typed right count.
+        let quote = QUOTES[0];
+        let mut game = quote.game(settings);
+        let mut right = true;
+        let mut typed_right = 0;
+        while !game.is_finished() {
+            assert!(!game.is_game_over());
+            if let Some(focus) = game.board().get_focused() {
+                let word = focus.assigned_text().to_string();
+                if right {
+                    for ch in word.chars() {
+                        game.handle_event(Event::Type(ch));
+                    }
+                    typed_right += 1;
+                }
+                game.handle_event(Event::Next);
+                right = !right;
+            }
+            game.handle_event(Event::Tick(1_000.0));
+        }
+        let WordSource::Text(passage) = game.words() else {
+            unreachable!()
+        };
+        // Long words are split, but nothing is left out or moved.
+        assert_eq!(passage.words().concat(), quote.text.replace(' ', ""));
+        assert_eq!(passage.typed(), typed_right);
+        assert!(passage.typed_fraction() < 1.0);
+    }
+}
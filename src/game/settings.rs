@@ -1,5 +1,8 @@
 use getset::WithSetters;
 
+use super::board::WordSizing;
+use super::difficulty::WordDifficulty;
+
 #[derive(Debug, Clone, Copy, WithSetters, PartialEq)]
 pub struct Settings {
     #[getset(set_with = "pub")]
@@ -16,6 +19,45 @@ pub struct Settings {
     pub spawn_interval: f64,
     #[getset(set_with = "pub")]
     pub drift_interval: u8,
+    /// Seed for word and position picks. Games with the same settings and seed that receive the
+    /// same events play out exactly the same. A fresh seed is drawn for every game if unset.
+    #[getset(set_with = "pub")]
+    pub seed: Option<u64>,
+    /// Whether words may spawn wrapped across two rows in Tetris-like shapes, rather than always
+    /// as a single row.
+    #[getset(set_with = "pub")]
+    pub shaped_pieces: bool,
+    /// Chance of each spawned block being a bomb, wildcard, locking or ice block.
+    #[getset(set_with = "pub")]
+    pub special_block_chance: f64,
+    /// How long a movement key has to be held before it starts repeating.
+    #[getset(set_with = "pub")]
+    pub auto_shift_delay: f64,
+    /// Time between repeats of a held movement key. Zero moves all the way across at once.
+    #[getset(set_with = "pub")]
+    pub auto_repeat_interval: f64,
+    /// Longest step the game is simulated in. Steps are shortened to the fall or spawn interval if
+    /// either is shorter, so none of them are ever skipped, but never to less than a hundredth of a
+    /// millisecond.
+    #[getset(set_with = "pub")]
+    pub time_step: f64,
+    /// Most time a single tick will catch up on, like after the game was in a background tab.
+    #[getset(set_with = "pub")]
+    pub max_catch_up: f64,
+    /// Finish the game once this many words have been typed right and dropped, instead of
+    /// playing until the stack reaches the top.
+    #[getset(set_with = "pub")]
+    pub word_goal: Option<usize>,
+    /// How hard the words of spawned blocks are, by level.
+    #[getset(set_with = "pub")]
+    pub word_difficulty: WordDifficulty,
+    /// How many other words have to spawn before one can spawn again. Zero lets words repeat
+    /// right away. Words still repeat sooner if there aren't enough others that fit.
+    #[getset(set_with = "pub")]
+    pub repeat_window: usize,
+    /// Whether to favour words that fill the gaps in the stack, or ones that don't.
+    #[getset(set_with = "pub")]
+    pub word_sizing: WordSizing,
 }
 
 impl Default for Settings {
@@ -28,6 +70,17 @@ impl Default for Settings {
             spawn_interval: 4_000.0,
             fall_interval: 100.0,
             drift_interval: 8,
+            seed: None,
+            shaped_pieces: false,
+            special_block_chance: 0.0,
+            auto_shift_delay: 170.0,
+            auto_repeat_interval: 50.0,
+            time_step: 1_000.0 / 60.0,
+            max_catch_up: 1_000.0,
+            word_goal: None,
+            word_difficulty: WordDifficulty::Any,
+            repeat_window: 20,
+            word_sizing: WordSizing::Any,
         }
     }
 }
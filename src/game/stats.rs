@@ -0,0 +1,83 @@
+use getset::CopyGetters;
+
+/// Typing and clearing statistics of a game. Time is in the same unit as [`super::Event::Tick`],
+/// which is milliseconds in the app.
+#[derive(Debug, Clone, Copy, PartialEq, Default, CopyGetters)]
+pub struct Stats {
+    /// Time spent playing.
+    #[getset(get_copy = "pub")]
+    elapsed: f64,
+    /// Characters typed, right or wrong.
+    #[getset(get_copy = "pub")]
+    keystrokes: usize,
+    /// Characters typed that matched the character they were typed over.
+    #[getset(get_copy = "pub")]
+    correct_keystrokes: usize,
+    /// Correctly typed words that were dropped.
+    #[getset(get_copy = "pub")]
+    words: usize,
+    #[getset(get_copy = "pub")]
+    rows: usize,
+}
+
+impl Stats {
+    #[inline]
+    pub(super) fn record_time(&mut self, delta_time: f64) {
+        self.elapsed += delta_time;
+    }
+
+    #[inline]
+    pub(super) fn record_keystroke(&mut self, correct: bool) {
+        self.keystrokes += 1;
+        self.correct_keystrokes += correct as usize;
+    }
+
+    #[inline]
+    pub(super) fn record_word(&mut self) {
+        self.words += 1;
+    }
+
+    #[inline]
+    pub(super) fn record_rows(&mut self, rows: usize) {
+        self.rows += rows;
+    }
+
+    /// Words per minute, counting every five correct keystrokes as a word.
+    pub fn wpm(&self) -> f64 {
+        if self.elapsed <= 0.0 {
+            return 0.0;
+        }
+        // five characters a word, 60 000 ms a minute
+        self.correct_keystrokes as f64 * 12_000.0 / self.elapsed
+    }
+
+    /// Fraction of keystrokes that were correct, or 1 if nothing has been typed yet.
+    pub fn accuracy(&self) -> f64 {
+        if self.keystrokes == 0 {
+            1.0
+        } else {
+            self.correct_keystrokes as f64 / self.keystrokes as f64
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn t0() {
+        let mut stats = Stats::default();
+        assert_eq!(stats.wpm(), 0.0);
+        assert_eq!(stats.accuracy(), 1.0);
+
+        for i in 0..60 {
+            stats.record_keystroke(i % 4 != 0);
+        }
+        stats.record_time(30_000.0);
+        assert_eq!(stats.keystrokes(), 60);
+        assert_eq!(stats.correct_keystrokes(), 45);
+        assert_eq!(stats.accuracy(), 0.75);
+        assert_eq!(stats.wpm(), 18.0);
+    }
+}
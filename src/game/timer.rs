@@ -1,4 +1,4 @@
-use getset::{CopyGetters, Getters};
+use getset::CopyGetters;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, CopyGetters)]
 pub(super) struct Msg {
@@ -10,7 +10,7 @@ pub(super) struct Msg {
     should_drift: bool,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Getters)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub(super) struct Timer {
     drift_interval: u8,
     fall_count: u8,
@@ -18,8 +18,8 @@ pub(super) struct Timer {
     spawn_interval: f64,
     spawn_timer: f64,
     fall_timer: f64,
-    #[getset(get = "pub(super)")]
-    last_delta: f64,
+    /// Factor every interval is stretched by.
+    scale: f64,
 }
 
 impl Timer {
@@ -32,14 +32,32 @@ impl Timer {
             spawn_interval,
             spawn_timer: spawn_interval,
             fall_timer: fall_interval,
-            last_delta: 0.0,
+            scale: 1.0,
         }
     }
 
+    /// Stretch every interval by `scale`, or squeeze it if `scale` is less than 1.
+    #[inline]
+    pub(super) fn set_scale(&mut self, scale: f64) {
+        self.scale = scale;
+    }
+
+    /// Fraction of the fall interval that has passed, counting `pending` time that hasn't been
+    /// ticked yet.
+    #[inline]
+    pub(super) fn fall_progress(&self, pending: f64) -> f64 {
+        (1.0 - (self.fall_timer - pending / self.scale) / self.fall_interval).clamp(0.0, 1.0)
+    }
+
+    /// Whether the next fall moves interactable blocks too.
+    #[inline]
+    pub(super) fn drifts_next(&self) -> bool {
+        self.fall_count == 1
+    }
+
     pub(super) fn tick(&mut self, delta_time: f64) -> Msg {
-        self.last_delta = delta_time;
-        self.fall_timer -= delta_time;
-        self.spawn_timer -= delta_time;
+        self.fall_timer -= delta_time / self.scale;
+        self.spawn_timer -= delta_time / self.scale;
         let mut ret = Msg {
             should_spawn: self.spawn_timer <= 0.0,
             should_fall: self.fall_timer <= 0.0,
@@ -74,7 +92,6 @@ mod test {
                 should_drift: false
             }
         );
-        assert_eq!(timer.last_delta(), &0.7);
         assert_eq!(
             timer.tick(0.7),
             Msg {
@@ -83,7 +100,6 @@ mod test {
                 should_drift: false
             }
         );
-        assert_eq!(timer.last_delta(), &0.7);
         assert_eq!(
             timer.tick(0.8),
             Msg {
@@ -92,7 +108,6 @@ mod test {
                 should_drift: true
             }
         );
-        assert_eq!(timer.last_delta(), &0.8);
         assert_eq!(
             timer.tick(0.2),
             Msg {
@@ -101,6 +116,5 @@ mod test {
                 should_drift: false
             }
         );
-        assert_eq!(timer.last_delta(), &0.2);
     }
 }
@@ -0,0 +1,192 @@
+use getset::CopyGetters;
+use serde::{Deserialize, Serialize};
+
+use super::{Event, Game, Notice, settings::Settings};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Player {
+    One,
+    Two,
+}
+
+impl Player {
+    #[inline]
+    pub fn opponent(self) -> Self {
+        match self {
+            Player::One => Player::Two,
+            Player::Two => Player::One,
+        }
+    }
+
+    #[inline]
+    pub(super) fn index(self) -> usize {
+        match self {
+            Player::One => 0,
+            Player::Two => 1,
+        }
+    }
+}
+
+/// Two games played side by side, where clearing several rows at once sends garbage rows to the
+/// opponent. The match doesn't care where the events come from, so players can be local, remote
+/// or bots.
+#[derive(Debug, Clone, PartialEq, CopyGetters)]
+pub struct VersusMatch {
+    games: [Game; 2],
+    /// The player left standing after the other one topped out.
+    #[getset(get_copy = "pub")]
+    winner: Option<Player>,
+}
+
+impl Default for VersusMatch {
+    fn default() -> Self {
+        Self::new(Settings::default())
+    }
+}
+
+impl VersusMatch {
+    #[inline]
+    pub fn new(settings: Settings) -> Self {
+        let settings = settings.with_starts_with_splash(false);
+        Self {
+            games: [Game::new(settings), Game::new(settings)],
+            winner: None,
+        }
+    }
+
+    #[inline]
+    pub fn game(&self, player: Player) -> &Game {
+        &self.games[player.index()]
+    }
+
+    #[inline]
+    pub fn is_over(&self) -> bool {
+        self.winner.is_some()
+    }
+
+    /// Take the notices queued by the game of `player`, oldest first.
+    #[inline]
+    pub fn take_notices(&mut self, player: Player) -> Vec<Notice> {
+        self.games[player.index()].take_notices()
+    }
+
+    /// Handle an event from the given player and return a boolean indicating whether state has
+    /// changed. [`Event::NewGame`] restarts the whole match regardless of who sent it.
+    pub fn handle_event(&mut self, player: Player, event: Event) -> bool {
+        if let Event::NewGame = event {
+            *self = Self::new(*self.games[0].settings());
+            return true;
+        }
+        if self.is_over() {
+            return false;
+        }
+
+        let game = &mut self.games[player.index()];
+        let mut ret = game.handle_event(event);
+        let garbage = game.take_garbage();
+        let lost = game.is_game_over();
+        if garbage > 0 {
+            self.games[player.opponent().index()].receive_garbage(garbage);
+            ret = true;
+        }
+        if lost {
+            self.winner = Some(player.opponent());
+        }
+        ret
+    }
+
+    /// Advance both games by `delta_time`.
+    #[inline]
+    pub fn tick(&mut self, delta_time: f64) -> bool {
+        let a = self.handle_event(Player::One, Event::Tick(delta_time));
+        let b = self.handle_event(Player::Two, Event::Tick(delta_time));
+        a || b
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::{block::Block, board::Board};
+
+    fn settings() -> Settings {
+        Settings::default()
+            .with_starts_with_one(false)
+            .with_width(4)
+            .with_height(6)
+            .with_spawn_interval(100.0)
+            .with_fall_interval(1.0)
+            .with_drift_interval(1)
+    }
+
+    /// Set up player one's board so that two blocks settle at the same time on the second tick
+    /// and clear two rows at once.
+    fn double_clear(m: &mut VersusMatch) {
+        let board = &mut m.games[0].board;
+        board.push_block(Block::new_settled("ab", 2, 5));
+        board.push_block(Block::new_settled("ef", 2, 4));
+        board.push_block(Block::new_falling("cd", 0, 4));
+        board.push_block(Block::new_falling("gh", 0, 3));
+    }
+
+    #[test]
+    fn sends_garbage() {
+        let mut m = VersusMatch::new(settings());
+        double_clear(&mut m);
+        assert!(m.handle_event(Player::One, Event::Tick(1.0)));
+        assert!(m.handle_event(Player::One, Event::Tick(1.0)));
+        assert_eq!(m.game(Player::One).score(), 2);
+        assert_eq!(m.game(Player::One).outgoing_garbage(), 0);
+        assert_eq!(m.game(Player::Two).incoming_garbage(), 1);
+
+        // garbage rises once player two settles a block without clearing anything
+        m.games[1]
+            .board
+            .push_block(Block::new_interactable("ij", 0, 0));
+        for _ in 0..6 {
+            m.handle_event(Player::Two, Event::Tick(1.0));
+        }
+        let board: &Board = m.game(Player::Two).board();
+        assert_eq!(m.game(Player::Two).incoming_garbage(), 0);
+        let garbage = board
+            .blocks()
+            .iter()
+            .filter(|b| b.is_garbage())
+            .collect::<Vec<_>>();
+        assert_eq!(garbage.len(), 3);
+        assert!(garbage.iter().all(|b| b.position().y == 5));
+        assert!(
+            board
+                .blocks()
+                .iter()
+                .any(|b| !b.is_garbage() && b.position().y == 4)
+        );
+    }
+
+    #[test]
+    fn cancels_garbage() {
+        let mut m = VersusMatch::new(settings());
+        m.games[0].receive_garbage(3);
+        double_clear(&mut m);
+        m.handle_event(Player::One, Event::Tick(1.0));
+        m.handle_event(Player::One, Event::Tick(1.0));
+        assert_eq!(m.game(Player::One).incoming_garbage(), 2);
+        assert_eq!(m.game(Player::Two).incoming_garbage(), 0);
+    }
+
+    #[test]
+    fn top_out() {
+        let mut m = VersusMatch::new(settings());
+        m.games[1].receive_garbage(6);
+        m.games[1]
+            .board
+            .push_block(Block::new_interactable("ij", 0, 4));
+        m.handle_event(Player::Two, Event::Tick(1.0));
+        assert!(m.handle_event(Player::Two, Event::Tick(1.0)));
+        assert!(m.game(Player::Two).is_game_over());
+        assert_eq!(m.winner(), Some(Player::One));
+        assert!(!m.handle_event(Player::One, Event::Tick(1.0)));
+        assert!(m.handle_event(Player::Two, Event::NewGame));
+        assert_eq!(m.winner(), None);
+    }
+}
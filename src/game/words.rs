@@ -0,0 +1,148 @@
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::ops::RangeInclusive;
+
+use rand::{
+    Rng,
+    seq::{IndexedRandom, IteratorRandom},
+};
+
+use super::block::WORDS;
+use super::passage::Passage;
+use super::practice::KeyProfile;
+
+/// With at least this many words to pick from, [`WordSource::Letters`] doesn't make any up.
+const ENOUGH_WORDS: usize = 20;
+/// Lengths of made-up drills.
+const DRILL_LENGTHS: RangeInclusive<usize> = 2..=5;
+
+/// Something a spawned word should be, if there are words that are.
+pub(super) type Preference<'a> = &'a dyn Fn(&str) -> bool;
+
+/// Where the words of spawned blocks come from.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum WordSource {
+    /// Any word from the built-in list, all equally likely.
+    #[default]
+    Random,
+    /// Words from the built-in list, more likely the more of the player's most-missed letters and
+    /// bigrams they have in them. The profile learns from what's typed.
+    Practice(KeyProfile),
+    /// Words from the built-in list made only of these letters, ignoring case. If there are few
+    /// of them, made-up groups of the letters fill in half the time, the way typing drills do.
+    Letters(String),
+    /// The words of a text, in order. Nothing more spawns once they've all been.
+    Text(Passage),
+}
+
+/// A made-up group of `letters` that `fits`, or `None` if none do.
+fn drill(letters: &str, fits: impl Fn(&str) -> bool, rng: &mut impl Rng) -> Option<String> {
+    let letters: Vec<char> = letters.chars().collect();
+    let drills: Vec<String> = DRILL_LENGTHS
+        .map(|len| (0..len).filter_map(|_| letters.choose(rng)).collect())
+        .filter(|drill: &String| !drill.is_empty() && fits(drill))
+        .collect();
+    drills.into_iter().choose(rng)
+}
+
+impl WordSource {
+    /// Pick a word that `fits`, or `None` if no word does.
+    pub(super) fn pick(
+        &mut self,
+        fits: impl Fn(&str) -> bool,
+        rng: &mut impl Rng,
+    ) -> Option<Cow<'static, str>> {
+        let candidates = WORDS.iter().copied().filter(|w| fits(w));
+        match self {
+            Self::Random => candidates.choose(rng).map(Cow::Borrowed),
+            Self::Practice(profile) => candidates
+                .collect::<Vec<_>>()
+                .choose_weighted(rng, |w| profile.weight(w))
+                .ok()
+                .map(|&w| Cow::Borrowed(w)),
+            Self::Letters(letters) => {
+                let words: Vec<_> = candidates
+                    .filter(|w| w.chars().all(|c| letters.contains(c.to_ascii_lowercase())))
+                    .collect();
+                if words.len() >= ENOUGH_WORDS || (!words.is_empty() && rng.random_bool(0.5)) {
+                    words.choose(rng).map(|&w| Cow::Borrowed(w))
+                } else {
+                    drill(letters, fits, rng).map(Cow::Owned)
+                }
+            }
+            Self::Text(passage) => passage.pick(fits).map(Cow::Owned),
+        }
+    }
+
+    /// Learn from typing `expected`, which came after `previous` in its word, right or wrong.
+    pub(super) fn record_keystroke(
+        &mut self,
+        expected: char,
+        previous: Option<char>,
+        correct: bool,
+    ) {
+        if let Self::Practice(profile) = self {
+            profile.record(expected, previous, correct);
+        }
+    }
+
+    /// Count a word from this source typed right.
+    pub(super) fn record_word(&mut self) {
+        if let Self::Text(passage) = self {
+            passage.record_typed();
+        }
+    }
+
+    /// Get ready for a new game.
+    pub(super) fn restart(&mut self) {
+        match self {
+            Self::Text(passage) => passage.restart(),
+            Self::Random | Self::Letters(_) | Self::Practice(_) => (),
+        }
+    }
+
+    /// Whether there are no more words to pick.
+    pub fn is_exhausted(&self) -> bool {
+        matches!(self, Self::Text(passage) if passage.is_exhausted())
+    }
+
+    /// What the player has been getting wrong, if this source keeps track.
+    pub fn key_profile(&self) -> Option<&KeyProfile> {
+        match self {
+            Self::Practice(profile) => Some(profile),
+            Self::Random | Self::Letters(_) | Self::Text(_) => None,
+        }
+    }
+}
+
+/// The last few words spawned, to keep them from coming up again too soon.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(super) struct RecentWords {
+    /// How many words are remembered.
+    window: usize,
+    words: VecDeque<String>,
+}
+
+impl RecentWords {
+    pub(super) fn new(window: usize) -> Self {
+        Self {
+            window,
+            words: VecDeque::with_capacity(window),
+        }
+    }
+
+    pub(super) fn contains(&self, word: &str) -> bool {
+        self.words.iter().any(|w| w == word)
+    }
+
+    /// Remember `word`, forgetting the oldest if there are too many.
+    pub(super) fn push(&mut self, word: &str) {
+        if self.window == 0 {
+            return;
+        }
+        if self.words.len() == self.window {
+            self.words.pop_front();
+        }
+        self.words.push_back(word.to_owned());
+    }
+}
@@ -0,0 +1,96 @@
+//! Messages exchanged between versus clients and the relay server, sent as JSON text frames over
+//! WebSocket.
+//!
+//! Clients never send boards. Both games of a match are seeded with the same seed, and each client
+//! streams the events that drive its own game, so the opponent can simulate it exactly. The
+//! relay server only pairs clients up and forwards everything but [`Message::Join`] to the
+//! opponent.
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::{Event, versus::Player};
+
+/// Port the relay server listens on unless told otherwise.
+pub const DEFAULT_PORT: u16 = 9001;
+
+/// A [`Message::StateHash`] is sent every this many stream messages.
+pub const HASH_INTERVAL: u32 = 60;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum Message {
+    /// Client to server: wait for an opponent in `room`.
+    Join { room: String },
+    /// Server to client: an opponent has joined. Both games are seeded with `seed`.
+    Start { seed: u64, player: Player },
+    /// An event fed to the sender's game. This includes ticks, so the opponent's copy advances
+    /// by exactly the same amounts.
+    Input { event: Event },
+    /// The sender queued `rows` garbage rows from its opponent at this point in its stream.
+    Garbage { rows: usize },
+    /// [`crate::game::Game::state_hash`] of the sender's game after its first `frame`
+    /// [`Message::Input`] and [`Message::Garbage`] messages.
+    StateHash { frame: u32, hash: u64 },
+    /// The sender topped out.
+    GameOver,
+    /// Server to client: the opponent disconnected.
+    OpponentLeft,
+}
+
+impl Message {
+    #[inline]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("messages are always serializable")
+    }
+
+    #[inline]
+    pub fn from_json(text: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(text)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for message in [
+            Message::Join {
+                room: "lunch".into(),
+            },
+            Message::Start {
+                seed: u64::MAX,
+                player: Player::Two,
+            },
+            Message::Input {
+                event: Event::Tick(33.300000000000004),
+            },
+            Message::Input {
+                event: Event::Type('q'),
+            },
+            Message::Input { event: Event::Next },
+            Message::Garbage { rows: 3 },
+            Message::StateHash {
+                frame: 120,
+                hash: 0xdead_beef_dead_beef,
+            },
+            Message::GameOver,
+            Message::OpponentLeft,
+        ] {
+            assert_eq!(Message::from_json(&message.to_json()).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn wire_format() {
+        assert_eq!(
+            Message::Input {
+                event: Event::Type('a')
+            }
+            .to_json(),
+            r#"{"type":"Input","event":{"Type":"a"}}"#
+        );
+        assert!(Message::from_json(r#"{"type":"Shrug"}"#).is_err());
+    }
+}